@@ -0,0 +1,76 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use r#box::Symbol;
+
+fn hit(criterion: &mut Criterion) {
+    let values = (0..1000).map(|index| format!("hit-{index}"));
+    let values = values.collect::<Vec<_>>();
+    values.iter().for_each(|value| {
+        Symbol::new(value.as_str());
+    });
+    criterion.bench_function("hit", |bencher| {
+        bencher.iter(|| {
+            for value in &values {
+                Symbol::new(value.as_str());
+            }
+        })
+    });
+}
+
+fn miss(criterion: &mut Criterion) {
+    let mut index = 0usize;
+    criterion.bench_function("miss", |bencher| {
+        bencher.iter(|| {
+            index += 1;
+            Symbol::new(format!("miss-{index}"))
+        })
+    });
+}
+
+// Interning distinct strings from several threads at once, which is limited by
+// the lock assigning identifiers.
+fn miss_parallel(criterion: &mut Criterion) {
+    let mut index = 0usize;
+    criterion.bench_function("miss_parallel", |bencher| {
+        bencher.iter(|| {
+            index += 1;
+            std::thread::scope(|scope| {
+                for thread in 0..THREADS {
+                    scope.spawn(move || {
+                        for value in 0..100 {
+                            Symbol::new(format!("miss-parallel-{index}-{thread}-{value}"));
+                        }
+                    });
+                }
+            })
+        })
+    });
+}
+
+// Looking up interned strings from several threads at once, which only locks
+// the shards for reading.
+fn hit_parallel(criterion: &mut Criterion) {
+    let values = (0..1000).map(|index| format!("hit-parallel-{index}"));
+    let values = values.collect::<Vec<_>>();
+    values.iter().for_each(|value| {
+        Symbol::new(value.as_str());
+    });
+    criterion.bench_function("hit_parallel", |bencher| {
+        bencher.iter(|| {
+            std::thread::scope(|scope| {
+                for thread in 0..THREADS {
+                    let values = &values;
+                    scope.spawn(move || {
+                        for value in values.iter().skip(thread).step_by(THREADS) {
+                            Symbol::new(value.as_str());
+                        }
+                    });
+                }
+            })
+        })
+    });
+}
+
+const THREADS: usize = 4;
+
+criterion_group!(benches, hit, miss, hit_parallel, miss_parallel);
+criterion_main!(benches);
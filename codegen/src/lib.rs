@@ -0,0 +1,170 @@
+//! Generator of symbols known at compile time.
+//!
+//! The generated module contains a constant per string with an identifier
+//! fixed via `Config::reserved` and a function configuring the storage
+//! accordingly, so that the constants are used without interning. The function
+//! has to be called before the storage is used, and the constants refer to
+//! other strings if it fails.
+//!
+//! ## Example
+//!
+//! In `build.rs`:
+//!
+//! ```no_run
+//! let path = std::env::var("OUT_DIR").unwrap();
+//! let path = std::path::Path::new(&path).join("symbols.rs");
+//! box_codegen::write(path, ["GET", "POST"]).unwrap();
+//! ```
+//!
+//! In the crate:
+//!
+//! ```ignore
+//! mod symbols {
+//!     include!(concat!(env!("OUT_DIR"), "/symbols.rs"));
+//! }
+//!
+//! symbols::configure(Default::default()).unwrap();
+//! assert_eq!(symbols::GET, "GET");
+//! ```
+
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::path::Path;
+
+/// An error.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The string is empty.
+    Empty,
+    /// The string is given more than once.
+    Duplicate(String),
+    /// The string does not give a unique name.
+    Name(String),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Empty => formatter.write_str("the string is empty"),
+            Self::Duplicate(value) => write!(formatter, "the string {value:?} is given twice"),
+            Self::Name(value) => write!(
+                formatter,
+                "the string {value:?} does not give a unique name"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Generate a module for strings.
+pub fn generate<I, T>(values: I) -> Result<String, Error>
+where
+    I: IntoIterator<Item = T>,
+    T: AsRef<str>,
+{
+    let values = values.into_iter().collect::<Vec<_>>();
+    let mut seen = HashSet::new();
+    let mut names = ["RESERVED".to_string()].into_iter().collect::<HashSet<_>>();
+    let mut constants = String::new();
+    for (index, value) in values.iter().enumerate() {
+        let value = value.as_ref();
+        if value.is_empty() {
+            return Err(Error::Empty);
+        }
+        if !seen.insert(value) {
+            return Err(Error::Duplicate(value.into()));
+        }
+        let name = name(value);
+        if name.chars().all(|character| character == '_') || !names.insert(name.clone()) {
+            return Err(Error::Name(value.into()));
+        }
+        let _ = writeln!(constants, "\n/// The symbol of {value:?}.");
+        let _ = writeln!(
+            constants,
+            "pub const {name}: r#box::Symbol = r#box::Symbol::reserved({});",
+            index + 1,
+        );
+    }
+    let mut output = String::new();
+    output.push_str("// Generated by box-codegen.\n\n");
+    output.push_str("/// The strings in the order of their identifiers.\n");
+    output.push_str("pub const RESERVED: &[&str] = &[\n");
+    for value in &values {
+        let _ = writeln!(output, "    {:?},", value.as_ref());
+    }
+    output.push_str("];\n");
+    output.push_str(&constants);
+    output.push_str(concat!(
+        "\n/// Configure the storage with the strings reserved.\n",
+        "pub fn configure(config: r#box::Config) -> Result<(), r#box::ConfigureError> {\n",
+        "    r#box::configure(r#box::Config {\n",
+        "        reserved: RESERVED,\n",
+        "        ..config\n",
+        "    })\n",
+        "}\n",
+    ));
+    Ok(output)
+}
+
+/// Generate a module for strings and write it to a file.
+pub fn write<P, I, T>(path: P, values: I) -> std::io::Result<()>
+where
+    P: AsRef<Path>,
+    I: IntoIterator<Item = T>,
+    T: AsRef<str>,
+{
+    let output = generate(values).map_err(std::io::Error::other)?;
+    std::fs::write(path, output)
+}
+
+/// Return the name of the constant for a string.
+///
+/// Letters are converted to uppercase, other characters except digits are
+/// replaced with underscores, and an underscore is prepended if the string
+/// starts with a digit.
+pub fn name(value: &str) -> String {
+    let mut name = value
+        .chars()
+        .map(|character| match character {
+            'a'..='z' | 'A'..='Z' | '0'..='9' => character.to_ascii_uppercase(),
+            _ => '_',
+        })
+        .collect::<String>();
+    if name.starts_with(|character: char| character.is_ascii_digit()) {
+        name.insert(0, '_');
+    }
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Error;
+
+    #[test]
+    fn generate() {
+        let output = super::generate(["GET", "content-type", "2xx"]).unwrap();
+        assert!(output.contains("pub const RESERVED: &[&str] = &[\n    \"GET\",\n"));
+        assert!(output.contains("pub const GET: r#box::Symbol = r#box::Symbol::reserved(1);"));
+        assert!(
+            output.contains("pub const CONTENT_TYPE: r#box::Symbol = r#box::Symbol::reserved(2);")
+        );
+        assert!(output.contains("pub const _2XX: r#box::Symbol = r#box::Symbol::reserved(3);"));
+        assert!(output.contains("pub fn configure("));
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(super::generate([""]), Err(Error::Empty));
+        assert_eq!(
+            super::generate(["a", "a"]),
+            Err(Error::Duplicate("a".into()))
+        );
+        assert_eq!(super::generate(["a", "A"]), Err(Error::Name("A".into())));
+        assert_eq!(super::generate(["-"]), Err(Error::Name("-".into())));
+        assert_eq!(
+            super::generate(["reserved"]),
+            Err(Error::Name("reserved".into()))
+        );
+    }
+}
@@ -0,0 +1,13 @@
+use r#box::{Config, Symbol};
+
+mod symbols {
+    include!("data/symbols.rs");
+}
+
+#[test]
+fn configured() {
+    assert_eq!(symbols::configure(Config::default()), Ok(()));
+    assert_eq!(Symbol::new("PUT").id(), 3);
+    assert_eq!(Symbol::new("POST"), symbols::POST);
+    assert_eq!(symbols::GET, "GET");
+}
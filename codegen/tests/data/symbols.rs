@@ -0,0 +1,21 @@
+// Generated by box-codegen.
+
+/// The strings in the order of their identifiers.
+pub const RESERVED: &[&str] = &[
+    "GET",
+    "POST",
+];
+
+/// The symbol of "GET".
+pub const GET: r#box::Symbol = r#box::Symbol::reserved(1);
+
+/// The symbol of "POST".
+pub const POST: r#box::Symbol = r#box::Symbol::reserved(2);
+
+/// Configure the storage with the strings reserved.
+pub fn configure(config: r#box::Config) -> Result<(), r#box::ConfigureError> {
+    r#box::configure(r#box::Config {
+        reserved: RESERVED,
+        ..config
+    })
+}
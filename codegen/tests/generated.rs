@@ -0,0 +1,15 @@
+use r#box::{Config, ConfigureError, Symbol};
+
+mod symbols {
+    include!("data/symbols.rs");
+}
+
+#[test]
+fn generated() {
+    let output = box_codegen::generate(["GET", "POST"]).unwrap();
+    assert_eq!(output, include_str!("data/symbols.rs"));
+    let one = Symbol::new("PUT");
+    assert_eq!(symbols::configure(Config::default()), Err(ConfigureError));
+    assert_eq!(symbols::GET, one);
+    assert_eq!(Symbol::from_id(symbols::POST.id()), None);
+}
@@ -0,0 +1,193 @@
+//! Macros for the box package.
+//!
+//! The macros are to be used via the reexports of the box package with
+//! feature `macros` enabled.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, Ident, LitStr};
+
+/// Derive conversions between the unit variants of an enum and symbols.
+///
+/// The enum receives `fn symbol(&self) -> Symbol` and
+/// `fn from_symbol(Symbol) -> Option<Self>`. The string of a variant is its
+/// name unless given via `#[symbol(rename = "...")]`.
+#[proc_macro_derive(SymbolEnum, attributes(symbol))]
+pub fn symbol_enum(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match derive_symbol_enum(&input) {
+        Ok(output) => output.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// Define a lazily created symbol per nonempty line of a file.
+///
+/// The path is relative to the directory of the manifest of the calling
+/// package, and the names are derived via `box_codegen::name`.
+#[proc_macro]
+pub fn include_symbols(input: TokenStream) -> TokenStream {
+    let path = parse_macro_input!(input as LitStr);
+    match include(&path) {
+        Ok(output) => output.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// Derive symbols of the field names of a struct.
+///
+/// The struct receives `fn fields() -> &'static [LazySymbol]` with the fields
+/// in the order of declaration, `fn field_index(Symbol) -> Option<usize>`, and
+/// `fn <field>_symbol() -> Symbol` per field. The string of a field is its
+/// name unless given via `#[symbol(rename = "...")]`.
+#[proc_macro_derive(FieldSymbols, attributes(symbol))]
+pub fn field_symbols(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match derive_field_symbols(&input) {
+        Ok(output) => output.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn derive_field_symbols(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(input, "expected a struct"));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(Error::new_spanned(input, "expected named fields"));
+    };
+    let mut accessors = Vec::new();
+    let mut values = Vec::new();
+    for field in &fields.named {
+        let name = field.ident.as_ref().unwrap();
+        let name = name.to_string();
+        let name = name.strip_prefix("r#").unwrap_or(&name);
+        accessors.push(Ident::new(&format!("{name}_symbol"), field.span()));
+        values.push(rename(&field.attrs)?.unwrap_or_else(|| name.to_string()));
+    }
+    let indices = (0..values.len()).collect::<Vec<_>>();
+    let count = values.len();
+    let name = &input.ident;
+    let (implementation, type_, clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #implementation #name #type_ #clause {
+            /// Return the symbols of the fields in the order of declaration.
+            pub fn fields() -> &'static [::r#box::LazySymbol] {
+                static FIELDS: [::r#box::LazySymbol; #count] = [
+                    #(::r#box::LazySymbol::new(#values),)*
+                ];
+                &FIELDS
+            }
+
+            /// Find the position of a field by its symbol.
+            pub fn field_index(symbol: ::r#box::Symbol) -> ::core::option::Option<usize> {
+                match symbol.as_static_str() {
+                    #(#values => ::core::option::Option::Some(#indices),)*
+                    _ => ::core::option::Option::None,
+                }
+            }
+
+            #(
+                /// Return the symbol of the field.
+                pub fn #accessors() -> ::r#box::Symbol {
+                    Self::fields()[#indices].get()
+                }
+            )*
+        }
+    })
+}
+
+fn derive_symbol_enum(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let Data::Enum(data) = &input.data else {
+        return Err(Error::new_spanned(input, "expected an enum"));
+    };
+    let mut names = Vec::new();
+    let mut values = Vec::new();
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(Error::new_spanned(variant, "expected a unit variant"));
+        }
+        names.push(&variant.ident);
+        values.push(rename(&variant.attrs)?.unwrap_or_else(|| variant.ident.to_string()));
+    }
+    let name = &input.ident;
+    let (implementation, type_, clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #implementation #name #type_ #clause {
+            /// Return the symbol of the variant.
+            pub fn symbol(&self) -> ::r#box::Symbol {
+                match self {
+                    #(
+                        Self::#names => {
+                            static SYMBOL: ::r#box::LazySymbol = ::r#box::LazySymbol::new(#values);
+                            *SYMBOL
+                        }
+                    )*
+                }
+            }
+
+            /// Find the variant of a symbol.
+            pub fn from_symbol(symbol: ::r#box::Symbol) -> ::core::option::Option<Self> {
+                match symbol.as_static_str() {
+                    #(#values => ::core::option::Option::Some(Self::#names),)*
+                    _ => ::core::option::Option::None,
+                }
+            }
+        }
+    })
+}
+
+fn rename(attributes: &[syn::Attribute]) -> Result<Option<String>, Error> {
+    let mut value = None;
+    for attribute in attributes {
+        if !attribute.path().is_ident("symbol") {
+            continue;
+        }
+        attribute.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                value = Some(meta.value()?.parse::<LitStr>()?.value());
+                Ok(())
+            } else {
+                Err(meta.error("expected `rename`"))
+            }
+        })?;
+    }
+    Ok(value)
+}
+
+fn include(path: &LitStr) -> Result<TokenStream2, Error> {
+    let root = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let full = Path::new(&root).join(path.value());
+    let content = std::fs::read_to_string(&full).map_err(|error| {
+        let message = format!("failed to read {}: {error}", full.display());
+        Error::new(path.span(), message)
+    })?;
+    let full = full.to_string_lossy();
+    let mut names = HashSet::new();
+    let mut items = Vec::new();
+    for value in content.lines().map(str::trim) {
+        if value.is_empty() {
+            continue;
+        }
+        let name = box_codegen::name(value);
+        if name.chars().all(|character| character == '_') || !names.insert(name.clone()) {
+            let message = format!("the string {value:?} does not give a unique name");
+            return Err(Error::new(path.span(), message));
+        }
+        let name = Ident::new(&name, path.span());
+        let doc = format!("The symbol of {value:?}.");
+        items.push(quote! {
+            #[doc = #doc]
+            pub static #name: ::r#box::LazySymbol = ::r#box::LazySymbol::new(#value);
+        });
+    }
+    Ok(quote! {
+        const _: &[u8] = include_bytes!(#full);
+        #(#items)*
+    })
+}
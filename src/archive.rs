@@ -0,0 +1,72 @@
+use rkyv::rancor::{Fallible, Source};
+use rkyv::rc::{ArchivedRc, Flavor, RcResolver};
+use rkyv::ser::{Sharing, Writer};
+use rkyv::{Archive, Deserialize, Place, Serialize};
+
+use crate::Symbol;
+
+/// An archived symbol.
+///
+/// Each string is archived once and shared by all occurrences of the symbol,
+/// and it can be read in place without interning it.
+pub type ArchivedSymbol = ArchivedRc<str, SymbolFlavor>;
+
+/// The flavor of `ArchivedSymbol`.
+pub struct SymbolFlavor;
+
+impl Flavor for SymbolFlavor {
+    const ALLOW_CYCLES: bool = false;
+}
+
+impl Archive for Symbol {
+    type Archived = ArchivedSymbol;
+    type Resolver = RcResolver;
+
+    #[inline]
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        ArchivedRc::resolve_from_ref(self.as_static_str(), resolver, out);
+    }
+}
+
+impl<S> Serialize<S> for Symbol
+where
+    S: Fallible + Writer + Sharing + ?Sized,
+    S::Error: Source,
+{
+    #[inline]
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedSymbol::serialize_from_ref(self.as_static_str(), serializer)
+    }
+}
+
+impl<D> Deserialize<Symbol, D> for ArchivedSymbol
+where
+    D: Fallible + ?Sized,
+    D::Error: Source,
+{
+    #[inline]
+    fn deserialize(&self, _: &mut D) -> Result<Symbol, D::Error> {
+        Symbol::try_new(self.get()).map_err(D::Error::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rkyv::rancor::Error;
+    use rkyv::vec::ArchivedVec;
+
+    use super::ArchivedSymbol;
+    use crate::Symbol;
+
+    #[test]
+    fn archive() {
+        let one = Symbol::new("archive-foo");
+        let symbols = vec![one; 10];
+        let bytes = rkyv::to_bytes::<Error>(&symbols).unwrap();
+        assert!(bytes.len() < 10 * one.len());
+        let archived = rkyv::access::<ArchivedVec<ArchivedSymbol>, Error>(&bytes).unwrap();
+        assert!(archived.iter().all(|symbol| symbol.get() == "archive-foo"));
+        let other = rkyv::deserialize::<Vec<Symbol>, Error>(archived).unwrap();
+        assert_eq!(other, symbols);
+    }
+}
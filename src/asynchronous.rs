@@ -0,0 +1,99 @@
+use core::future::Future;
+use core::hash::BuildHasher;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use crate::{cache, Box, Symbol};
+
+impl Symbol {
+    /// Create a new instance without blocking the executor.
+    ///
+    /// Instead of waiting for a lock held by another thread, the function
+    /// yields to the executor and retries later. The function panics if the
+    /// limit set via `limit` is reached or the storage is frozen via `freeze`.
+    pub async fn new_async<T>(value: T) -> Self
+    where
+        T: AsRef<str>,
+    {
+        let value = value.as_ref();
+        if value.is_empty() {
+            return Self::EMPTY;
+        }
+        let state = Box::instance();
+        let folded = state.fold(value);
+        let value = folded.as_deref().unwrap_or(value);
+        let hash = state.hasher.hash_one(value);
+        let epoch = cache::epoch();
+        if let Some(symbol) = cache::get(hash, value, epoch) {
+            return state.hit(symbol, value.len());
+        }
+        loop {
+            match state.try_intern(hash, value) {
+                Some(Ok(symbol)) => {
+                    cache::put(hash, symbol, epoch);
+                    return symbol;
+                }
+                Some(Err(error)) => panic!("{error}"),
+                _ => Yield(false).await,
+            }
+        }
+    }
+}
+
+// A future that is pending once, so that the executor runs other tasks.
+struct Yield(bool);
+
+impl Future for Yield {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.0 {
+            return Poll::Ready(());
+        }
+        self.0 = true;
+        context.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future::Future;
+    use std::hash::BuildHasher;
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Wake};
+
+    use crate::Symbol;
+
+    struct Waker;
+
+    impl Wake for Waker {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    fn block_on<T: Future>(future: T) -> T::Output {
+        let waker = Arc::new(Waker).into();
+        let mut context = Context::from_waker(&waker);
+        let mut future = std::pin::pin!(future);
+        loop {
+            if let Poll::Ready(value) = future.as_mut().poll(&mut context) {
+                return value;
+            }
+        }
+    }
+
+    #[test]
+    fn new_async() {
+        let symbol = block_on(Symbol::new_async("async-foo"));
+        assert_eq!(symbol, Symbol::new("async-foo"));
+        assert_eq!(block_on(Symbol::new_async("")), Symbol::EMPTY);
+        let state = crate::Box::instance();
+        let shard = state.shard(state.hasher.hash_one("async-bar")).write();
+        let mut future = std::pin::pin!(Symbol::new_async("async-bar"));
+        let waker = Arc::new(Waker).into();
+        let mut context = Context::from_waker(&waker);
+        assert!(future.as_mut().poll(&mut context).is_pending());
+        drop(shard);
+        assert_eq!(block_on(future), "async-bar");
+    }
+}
@@ -0,0 +1,40 @@
+use string_cache::{Atom, StaticAtomSet};
+
+use crate::Symbol;
+
+impl<S: StaticAtomSet> From<Atom<S>> for Symbol {
+    #[inline]
+    fn from(value: Atom<S>) -> Self {
+        Self::new(&*value)
+    }
+}
+
+impl<S: StaticAtomSet> From<&Atom<S>> for Symbol {
+    #[inline]
+    fn from(value: &Atom<S>) -> Self {
+        Self::new(&**value)
+    }
+}
+
+impl<S: StaticAtomSet> From<Symbol> for Atom<S> {
+    #[inline]
+    fn from(value: Symbol) -> Self {
+        Self::from(value.as_static_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use string_cache::DefaultAtom;
+
+    use crate::Symbol;
+
+    #[test]
+    fn atom() {
+        let atom = DefaultAtom::from("atom-foo");
+        let one = Symbol::from(&atom);
+        assert_eq!(one.as_static_str(), "atom-foo");
+        assert_eq!(Symbol::from(atom.clone()), one);
+        assert_eq!(DefaultAtom::from(one), atom);
+    }
+}
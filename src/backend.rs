@@ -0,0 +1,77 @@
+//! Storage behind an interface.
+
+use alloc::borrow::Cow;
+
+use crate::front::FrontCoded;
+use crate::Interner;
+
+/// A storage that can be used in place of `Interner`.
+///
+/// The trait is implemented by `Interner`, which is used by default, and by
+/// `front::FrontCoded`, and can be implemented downstream in order to
+/// experiment with other layouts, which can then be used via `scoped_with` and
+/// `serde::InternerSeed`. Strings are returned borrowed or assembled, so that
+/// storages not keeping whole strings can implement the trait too.
+pub trait Backend {
+    /// Store a string unless already stored and return its identifier.
+    fn intern(&mut self, value: &str) -> usize;
+
+    /// Find the identifier of a string without storing it.
+    fn get(&self, value: &str) -> Option<usize>;
+
+    /// Return the string by its identifier.
+    fn resolve(&self, id: usize) -> Option<Cow<'_, str>>;
+
+    /// Return the number of strings stored.
+    fn len(&self) -> usize;
+
+    /// Check if no strings are stored.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Backend for Interner {
+    #[inline]
+    fn intern(&mut self, value: &str) -> usize {
+        Interner::intern(self, value)
+    }
+
+    #[inline]
+    fn get(&self, value: &str) -> Option<usize> {
+        Interner::get(self, value)
+    }
+
+    #[inline]
+    fn resolve(&self, id: usize) -> Option<Cow<'_, str>> {
+        Interner::resolve(self, id).map(Cow::Borrowed)
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        Interner::len(self)
+    }
+}
+
+impl Backend for FrontCoded {
+    #[inline]
+    fn intern(&mut self, value: &str) -> usize {
+        FrontCoded::intern(self, value)
+    }
+
+    #[inline]
+    fn get(&self, value: &str) -> Option<usize> {
+        FrontCoded::get(self, value)
+    }
+
+    #[inline]
+    fn resolve(&self, id: usize) -> Option<Cow<'_, str>> {
+        FrontCoded::resolve(self, id).map(Cow::Owned)
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        FrontCoded::len(self)
+    }
+}
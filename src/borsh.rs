@@ -0,0 +1,104 @@
+//! Serialization via borsh.
+//!
+//! Symbols are serialized as strings. Containers with many repeated symbols
+//! can be serialized via `with_table` instead.
+
+use alloc::string::{String, ToString};
+
+use ::borsh::io::{Error, ErrorKind, Read, Result, Write};
+use ::borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::Symbol;
+
+impl BorshSerialize for Symbol {
+    #[inline]
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        self.as_static_str().serialize(writer)
+    }
+}
+
+impl BorshDeserialize for Symbol {
+    #[inline]
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        Symbol::try_new(String::deserialize_reader(reader)?)
+            .map_err(|error| Error::new(ErrorKind::InvalidData, error.to_string()))
+    }
+}
+
+/// Serialization of containers of symbols as a table of strings followed by
+/// references into it.
+///
+/// The functions are meant for `serialize_with` and `deserialize_with` of
+/// `#[borsh(...)]`, which store each string once regardless of how many times
+/// it occurs.
+pub mod with_table {
+    use alloc::string::{String, ToString};
+    use alloc::vec::Vec;
+
+    use ::borsh::io::{Error, ErrorKind, Read, Result, Write};
+    use ::borsh::{BorshDeserialize, BorshSerialize};
+
+    use crate::{InternError, Symbol};
+
+    /// Serialize a container of symbols.
+    pub fn serialize<T, W>(value: &T, writer: &mut W) -> Result<()>
+    where
+        for<'l> &'l T: IntoIterator<Item = &'l Symbol>,
+        W: Write,
+    {
+        let (strings, references) = crate::tabulate(value);
+        strings.serialize(writer)?;
+        let references = references
+            .into_iter()
+            .map(u32::try_from)
+            .collect::<core::result::Result<Vec<_>, _>>()
+            .map_err(|_| Error::new(ErrorKind::InvalidInput, "too many strings"))?;
+        references.serialize(writer)
+    }
+
+    /// Deserialize a container of symbols.
+    pub fn deserialize<T, R>(reader: &mut R) -> Result<T>
+    where
+        T: FromIterator<Symbol>,
+        R: Read,
+    {
+        let invalid = |error: InternError| Error::new(ErrorKind::InvalidData, error.to_string());
+        let symbols = Vec::<String>::deserialize_reader(reader)?
+            .into_iter()
+            .map(Symbol::try_new)
+            .collect::<core::result::Result<Vec<_>, _>>()
+            .map_err(invalid)?;
+        Vec::<u32>::deserialize_reader(reader)?
+            .into_iter()
+            .map(|id| symbols.get(id as usize).copied())
+            .map(|symbol| symbol.ok_or_else(|| invalid(InternError::InvalidId)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Symbol;
+
+    #[test]
+    fn borsh() {
+        let one = Symbol::new("borsh-foo");
+        let bytes = borsh::to_vec(&one).unwrap();
+        assert_eq!(bytes, borsh::to_vec("borsh-foo").unwrap());
+        assert_eq!(borsh::from_slice::<Symbol>(&bytes).unwrap(), one);
+    }
+
+    #[test]
+    fn with_table() {
+        use super::with_table;
+
+        let symbols = [Symbol::new("borsh-bar"), Symbol::new("borsh-baz")].repeat(2);
+        let mut bytes = Vec::new();
+        with_table::serialize(&symbols, &mut bytes).unwrap();
+        assert!(bytes.len() < borsh::to_vec(&symbols).unwrap().len());
+        let other: Vec<Symbol> = with_table::deserialize(&mut &bytes[..]).unwrap();
+        assert_eq!(other, symbols);
+        let bytes = borsh::to_vec(&(vec!["foo"], vec![1u32])).unwrap();
+        assert!(with_table::deserialize::<Vec<Symbol>, _>(&mut &bytes[..]).is_err());
+    }
+}
@@ -0,0 +1,209 @@
+//! Storage with eviction.
+
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+use hashbrown::HashTable;
+
+use crate::Hasher;
+
+/// A storage for unique strings evicting the least recently used ones.
+///
+/// The number of strings is bounded, and once the bound is reached, storing a
+/// new string evicts the one stored or looked up via `intern` the longest time
+/// ago. Handles carry a generation, so that a handle to an evicted string is
+/// detected instead of resolving to another string.
+///
+/// ## Example
+///
+/// ```
+/// use r#box::BoundedInterner;
+///
+/// let mut interner = BoundedInterner::new(2);
+/// let one = interner.intern("foo");
+/// let other = interner.intern("bar");
+/// interner.intern("foo");
+/// interner.intern("baz");
+/// assert_eq!(interner.resolve(one), Some("foo"));
+/// assert_eq!(interner.resolve(other), None);
+/// ```
+pub struct BoundedInterner {
+    capacity: usize,
+    slots: Vec<Slot>,
+    free: Vec<usize>,
+    head: Option<usize>,
+    tail: Option<usize>,
+    map: HashTable<usize>,
+    hasher: Hasher,
+}
+
+/// A handle to a string in a bounded storage.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BoundedSymbol {
+    index: usize,
+    generation: u64,
+}
+
+struct Slot {
+    value: Option<alloc::boxed::Box<str>>,
+    generation: u64,
+    previous: Option<usize>,
+    next: Option<usize>,
+}
+
+impl BoundedInterner {
+    /// Create an empty instance storing at most a number of strings.
+    ///
+    /// The function panics if the number is zero.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "the capacity should be positive");
+        Self {
+            capacity,
+            slots: Default::default(),
+            free: Default::default(),
+            head: None,
+            tail: None,
+            map: Default::default(),
+            hasher: Default::default(),
+        }
+    }
+
+    /// Store a string unless already stored, marking it as the most recently
+    /// used, and return its handle.
+    pub fn intern(&mut self, value: &str) -> BoundedSymbol {
+        let hash = self.hasher.hash_one(value);
+        if let Some(index) = self.find(hash, value) {
+            self.unlink(index);
+            self.link(index);
+            return self.handle(index);
+        }
+        if self.map.len() == self.capacity {
+            self.evict();
+        }
+        let index = match self.free.pop() {
+            Some(index) => index,
+            _ => {
+                self.slots.push(Slot {
+                    value: None,
+                    generation: 0,
+                    previous: None,
+                    next: None,
+                });
+                self.slots.len() - 1
+            }
+        };
+        self.slots[index].value = Some(value.into());
+        self.link(index);
+        let Self { slots, hasher, .. } = self;
+        self.map.insert_unique(hash, index, |&index| {
+            hasher.hash_one(slots[index].value.as_deref().unwrap())
+        });
+        self.handle(index)
+    }
+
+    /// Find the handle of a string without storing it or marking it as used.
+    #[inline]
+    pub fn get(&self, value: &str) -> Option<BoundedSymbol> {
+        let index = self.find(self.hasher.hash_one(value), value)?;
+        Some(self.handle(index))
+    }
+
+    /// Return the string by its handle unless evicted.
+    #[inline]
+    pub fn resolve(&self, symbol: BoundedSymbol) -> Option<&str> {
+        let slot = self.slots.get(symbol.index)?;
+        if slot.generation != symbol.generation {
+            return None;
+        }
+        slot.value.as_deref()
+    }
+
+    /// Return the maximum number of strings stored.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Return the number of strings stored.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Check if no strings are stored.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    fn evict(&mut self) {
+        let Some(index) = self.tail else {
+            return;
+        };
+        self.unlink(index);
+        let value = self.slots[index].value.take().unwrap();
+        let hash = self.hasher.hash_one(&*value);
+        if let Ok(entry) = self.map.find_entry(hash, |&other| other == index) {
+            entry.remove();
+        }
+        self.slots[index].generation += 1;
+        self.free.push(index);
+    }
+
+    fn find(&self, hash: u64, value: &str) -> Option<usize> {
+        let eq = |&index: &usize| self.slots[index].value.as_deref() == Some(value);
+        self.map.find(hash, eq).copied()
+    }
+
+    #[inline]
+    fn handle(&self, index: usize) -> BoundedSymbol {
+        BoundedSymbol {
+            index,
+            generation: self.slots[index].generation,
+        }
+    }
+
+    fn link(&mut self, index: usize) {
+        self.slots[index].previous = None;
+        self.slots[index].next = self.head;
+        match self.head {
+            Some(head) => self.slots[head].previous = Some(index),
+            _ => self.tail = Some(index),
+        }
+        self.head = Some(index);
+    }
+
+    fn unlink(&mut self, index: usize) {
+        let Slot { previous, next, .. } = self.slots[index];
+        match previous {
+            Some(previous) => self.slots[previous].next = next,
+            _ => self.head = next,
+        }
+        match next {
+            Some(next) => self.slots[next].previous = previous,
+            _ => self.tail = previous,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BoundedInterner;
+
+    #[test]
+    fn evict() {
+        let mut interner = BoundedInterner::new(2);
+        let foo = interner.intern("foo");
+        let bar = interner.intern("bar");
+        assert_eq!(interner.intern("foo"), foo);
+        let baz = interner.intern("baz");
+        assert_eq!(interner.len(), 2);
+        assert_eq!(interner.resolve(bar), None);
+        assert_eq!(interner.get("bar"), None);
+        assert_eq!(interner.resolve(foo), Some("foo"));
+        assert_eq!(interner.resolve(baz), Some("baz"));
+        let bar = interner.intern("bar");
+        assert_eq!(interner.resolve(foo), None);
+        assert_eq!(interner.resolve(bar), Some("bar"));
+        assert_eq!(interner.get("baz"), Some(baz));
+    }
+}
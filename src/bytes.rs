@@ -0,0 +1,177 @@
+//! Symbols for byte strings.
+
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+use hashbrown::hash_table::{Entry, HashTable};
+
+use crate::sync::Mutex;
+use crate::{Arena, Hasher};
+
+static STORAGE: Mutex<Option<Storage>> = Mutex::new(None);
+
+/// A unique static byte string.
+///
+/// Byte strings are stored separately from strings and need not be valid
+/// UTF-8.
+///
+/// ## Example
+///
+/// ```
+/// use r#box::ByteSymbol;
+///
+/// let one = ByteSymbol::new(b"foo\xff");
+/// let other = ByteSymbol::new(vec![b'f', b'o', b'o', 0xff]);
+/// assert_eq!(one, other);
+/// assert_eq!(one.len(), 4);
+/// ```
+#[derive(Clone, Copy)]
+pub struct ByteSymbol(&'static [u8]);
+
+#[derive(Default)]
+struct Storage {
+    map: HashTable<ByteSymbol>,
+    arena: Arena,
+    hasher: Hasher,
+}
+
+impl ByteSymbol {
+    /// Create a new instance.
+    pub fn new<T: AsRef<[u8]>>(value: T) -> Self {
+        let value = value.as_ref();
+        let mut storage = STORAGE.lock();
+        let Storage { map, arena, hasher } = storage.get_or_insert_with(Default::default);
+        let hash = hasher.hash_one(value);
+        let entry = map.entry(
+            hash,
+            |symbol| symbol.0 == value,
+            |symbol| hasher.hash_one(symbol.0),
+        );
+        match entry {
+            Entry::Occupied(entry) => *entry.get(),
+            Entry::Vacant(entry) => *entry.insert(Self(arena.store_bytes(value))).get(),
+        }
+    }
+
+    /// Find an existing instance without creating one.
+    pub fn get<T: AsRef<[u8]>>(value: T) -> Option<Self> {
+        let value = value.as_ref();
+        let storage = STORAGE.lock();
+        let storage = storage.as_ref()?;
+        let hash = storage.hasher.hash_one(value);
+        storage.map.find(hash, |symbol| symbol.0 == value).copied()
+    }
+
+    /// Return the number of instances.
+    pub fn count() -> usize {
+        STORAGE
+            .lock()
+            .as_ref()
+            .map_or(0, |storage| storage.map.len())
+    }
+
+    /// Return the underlying byte string.
+    #[inline]
+    pub fn as_static_bytes(&self) -> &'static [u8] {
+        self.0
+    }
+}
+
+impl AsRef<[u8]> for ByteSymbol {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.0
+    }
+}
+
+impl From<&[u8]> for ByteSymbol {
+    #[inline]
+    fn from(value: &[u8]) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<Vec<u8>> for ByteSymbol {
+    #[inline]
+    fn from(value: Vec<u8>) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<ByteSymbol> for Vec<u8> {
+    #[inline]
+    fn from(value: ByteSymbol) -> Self {
+        value.0.into()
+    }
+}
+
+impl core::borrow::Borrow<[u8]> for ByteSymbol {
+    #[inline]
+    fn borrow(&self) -> &[u8] {
+        self.0
+    }
+}
+
+impl core::cmp::Eq for ByteSymbol {}
+
+impl core::cmp::Ord for ByteSymbol {
+    #[inline]
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        if self == other {
+            return core::cmp::Ordering::Equal;
+        }
+        self.0.cmp(other.0)
+    }
+}
+
+impl core::cmp::PartialEq for ByteSymbol {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        core::ptr::eq(self.0, other.0)
+    }
+}
+
+impl core::cmp::PartialOrd for ByteSymbol {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl core::fmt::Debug for ByteSymbol {
+    fn fmt(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(formatter, "b\"{}\"", self.0.escape_ascii())
+    }
+}
+
+impl core::hash::Hash for ByteSymbol {
+    #[inline]
+    fn hash<T: core::hash::Hasher>(&self, state: &mut T) {
+        self.0.hash(state)
+    }
+}
+
+impl core::ops::Deref for ByteSymbol {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ByteSymbol;
+
+    #[test]
+    fn bytes() {
+        let one = ByteSymbol::new(b"bytes-\xff");
+        assert_eq!(ByteSymbol::from(b"bytes-\xff".to_vec()), one);
+        assert_eq!(ByteSymbol::get(b"bytes-\xff"), Some(one));
+        assert_eq!(ByteSymbol::get(b"bytes-\xfe"), None);
+        assert_eq!(one.as_static_bytes(), b"bytes-\xff");
+        assert_eq!(format!("{one:?}"), "b\"bytes-\\xff\"");
+        assert!(ByteSymbol::new(b"").is_empty());
+        assert!(ByteSymbol::count() > 0);
+    }
+}
@@ -0,0 +1,101 @@
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::Symbol;
+
+type Cache = RefCell<Vec<Option<(u64, Symbol, usize)>>>;
+
+static CAPACITY: AtomicUsize = AtomicUsize::new(64);
+
+// The number of times the caches of all threads have been invalidated, which
+// is recorded in each entry.
+static EPOCH: AtomicUsize = AtomicUsize::new(0);
+
+#[cfg(feature = "std")]
+thread_local! {
+    static CACHE: Cache = const { RefCell::new(Vec::new()) };
+}
+
+/// Set the number of entries in the cache each thread consults before
+/// looking up strings in the shared storage.
+///
+/// The cache is direct-mapped, so an entry is evicted when another string
+/// with a colliding hash is stored. Zero disables the cache. The default is
+/// 64. The cache is unavailable if feature `std` is disabled.
+pub fn set_thread_cache_capacity(capacity: usize) {
+    CAPACITY.store(capacity, Ordering::Relaxed);
+}
+
+#[inline]
+pub fn epoch() -> usize {
+    EPOCH.load(Ordering::Acquire)
+}
+
+pub fn invalidate() {
+    EPOCH.fetch_add(1, Ordering::AcqRel);
+}
+
+#[inline]
+pub fn get(hash: u64, value: &str, epoch: usize) -> Option<Symbol> {
+    let capacity = CAPACITY.load(Ordering::Relaxed);
+    if capacity == 0 {
+        return None;
+    }
+    let entry = with(|cache| {
+        let cache = cache.borrow();
+        *cache.get(locate(hash, cache.len())?)?
+    });
+    match entry {
+        Some(Some((other, symbol, other_epoch)))
+            if other == hash && other_epoch == epoch && symbol.as_static_str() == value =>
+        {
+            Some(symbol)
+        }
+        _ => None,
+    }
+}
+
+#[inline]
+pub fn put(hash: u64, symbol: Symbol, epoch: usize) {
+    let capacity = CAPACITY.load(Ordering::Relaxed);
+    if capacity == 0 {
+        return;
+    }
+    with(|cache| {
+        let mut cache = cache.borrow_mut();
+        if cache.len() != capacity {
+            cache.clear();
+            cache.resize(capacity, None);
+        }
+        let index = hash as usize % capacity;
+        cache[index] = Some((hash, symbol, epoch));
+    });
+}
+
+#[cfg(feature = "std")]
+#[inline]
+fn with<F, T>(function: F) -> Option<T>
+where
+    F: FnOnce(&Cache) -> T,
+{
+    CACHE.try_with(function).ok()
+}
+
+#[cfg(not(feature = "std"))]
+#[inline]
+fn with<F, T>(_: F) -> Option<T>
+where
+    F: FnOnce(&Cache) -> T,
+{
+    None
+}
+
+#[inline]
+fn locate(hash: u64, capacity: usize) -> Option<usize> {
+    if capacity == 0 {
+        None
+    } else {
+        Some(hash as usize % capacity)
+    }
+}
@@ -0,0 +1,16 @@
+use alloc::string::String;
+
+use ::quickcheck::{Arbitrary, Gen};
+
+use crate::Symbol;
+
+impl Arbitrary for Symbol {
+    #[inline]
+    fn arbitrary(generator: &mut Gen) -> Self {
+        Symbol::new(String::arbitrary(generator))
+    }
+
+    fn shrink(&self) -> alloc::boxed::Box<dyn Iterator<Item = Self>> {
+        alloc::boxed::Box::new(String::from(self.as_static_str()).shrink().map(Symbol::new))
+    }
+}
@@ -0,0 +1,109 @@
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use hashbrown::HashMap;
+use icu_collator::CollatorBorrowed;
+
+use crate::sync::RwLock;
+use crate::{Hasher, Symbol};
+
+/// A collator that caches the sort keys of symbols.
+///
+/// The sort key of a symbol is generated once per collator, so that sorting
+/// the same symbols repeatedly amounts to comparing bytes.
+///
+/// ## Example
+///
+/// ```
+/// use icu_collator::options::CollatorOptions;
+/// use icu_collator::Collator;
+/// use r#box::{Symbol, SymbolCollator};
+///
+/// let locale = icu_locale_core::locale!("sv").into();
+/// let collator = Collator::try_new(locale, CollatorOptions::default()).unwrap();
+/// let collator = SymbolCollator::new(collator);
+/// let mut symbols = ["ö", "z", "a"].map(Symbol::new);
+/// symbols.sort_by(|one, other| one.collate(other, &collator));
+/// assert_eq!(symbols, ["a", "z", "ö"]);
+/// ```
+pub struct SymbolCollator<'l> {
+    collator: CollatorBorrowed<'l>,
+    keys: RwLock<HashMap<usize, alloc::boxed::Box<[u8]>, Hasher>>,
+}
+
+impl<'l> SymbolCollator<'l> {
+    /// Create a new instance.
+    #[inline]
+    pub fn new(collator: CollatorBorrowed<'l>) -> Self {
+        Self {
+            collator,
+            keys: Default::default(),
+        }
+    }
+
+    /// Compare two symbols.
+    pub fn compare(&self, one: Symbol, other: Symbol) -> Ordering {
+        if one == other {
+            return Ordering::Equal;
+        }
+        let keys = self.keys.read();
+        if let (Some(one), Some(other)) = (keys.get(&one.id()), keys.get(&other.id())) {
+            return one.cmp(other);
+        }
+        drop(keys);
+        let mut keys = self.keys.write();
+        for symbol in [one, other] {
+            keys.entry(symbol.id())
+                .or_insert_with(|| self.generate(symbol));
+        }
+        keys[&one.id()].cmp(&keys[&other.id()])
+    }
+
+    /// Return the underlying collator.
+    #[inline]
+    pub fn collator(&self) -> &CollatorBorrowed<'l> {
+        &self.collator
+    }
+
+    fn generate(&self, symbol: Symbol) -> alloc::boxed::Box<[u8]> {
+        let mut key = Vec::new();
+        let Ok(()) = self
+            .collator
+            .write_sort_key_to(symbol.as_static_str(), &mut key);
+        key.into_boxed_slice()
+    }
+}
+
+impl Symbol {
+    /// Compare with another instance according to a collator.
+    #[inline]
+    pub fn collate(&self, other: &Self, collator: &SymbolCollator<'_>) -> Ordering {
+        collator.compare(*self, *other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cmp::Ordering;
+    use icu_collator::options::{CollatorOptions, Strength};
+    use icu_collator::Collator;
+
+    use super::SymbolCollator;
+    use crate::Symbol;
+
+    #[test]
+    fn collate() {
+        let mut options = CollatorOptions::default();
+        options.strength = Some(Strength::Primary);
+        let locale = icu_locale_core::locale!("en").into();
+        let collator = SymbolCollator::new(Collator::try_new(locale, options).unwrap());
+        let (one, other) = (Symbol::new("collate-Éa"), Symbol::new("collate-eb"));
+        assert_eq!(one.collate(&other, &collator), Ordering::Less);
+        assert_eq!(other.collate(&one, &collator), Ordering::Greater);
+        let another = Symbol::new("collate-ea");
+        assert_eq!(one.collate(&another, &collator), Ordering::Equal);
+        assert_eq!(
+            collator.collator().compare("collate-Éa", "collate-eb"),
+            Ordering::Less,
+        );
+    }
+}
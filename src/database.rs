@@ -0,0 +1,155 @@
+#[cfg(feature = "diesel")]
+mod diesel {
+    use ::diesel::backend::Backend;
+    use ::diesel::deserialize::{self, FromSql};
+    use ::diesel::serialize::{self, Output, ToSql};
+    use ::diesel::sql_types::Text;
+
+    use crate::Symbol;
+
+    impl<DB: Backend> ToSql<Text, DB> for Symbol
+    where
+        str: ToSql<Text, DB>,
+    {
+        #[inline]
+        fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+            self.as_static_str().to_sql(out)
+        }
+    }
+
+    impl<DB: Backend> FromSql<Text, DB> for Symbol
+    where
+        String: FromSql<Text, DB>,
+    {
+        #[inline]
+        fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+            Ok(Symbol::try_new(String::from_sql(bytes)?)?)
+        }
+    }
+}
+
+#[cfg(feature = "sqlx")]
+mod sqlx {
+    use ::sqlx::database::Database;
+    use ::sqlx::decode::Decode;
+    use ::sqlx::encode::{Encode, IsNull};
+    use ::sqlx::error::BoxDynError;
+    use ::sqlx::types::Type;
+
+    use crate::Symbol;
+
+    impl<DB: Database> Type<DB> for Symbol
+    where
+        str: Type<DB>,
+    {
+        #[inline]
+        fn type_info() -> DB::TypeInfo {
+            <str as Type<DB>>::type_info()
+        }
+
+        #[inline]
+        fn compatible(ty: &DB::TypeInfo) -> bool {
+            <str as Type<DB>>::compatible(ty)
+        }
+    }
+
+    impl<'q, DB: Database> Encode<'q, DB> for Symbol
+    where
+        &'q str: Encode<'q, DB>,
+    {
+        #[inline]
+        fn encode(self, buf: &mut DB::ArgumentBuffer) -> Result<IsNull, BoxDynError> {
+            <&str as Encode<DB>>::encode(self.as_static_str(), buf)
+        }
+
+        #[inline]
+        fn encode_by_ref(&self, buf: &mut DB::ArgumentBuffer) -> Result<IsNull, BoxDynError> {
+            <&str as Encode<DB>>::encode(self.as_static_str(), buf)
+        }
+
+        #[inline]
+        fn size_hint(&self) -> usize {
+            <&str as Encode<DB>>::size_hint(&self.as_static_str())
+        }
+    }
+
+    impl<'r, DB: Database> Decode<'r, DB> for Symbol
+    where
+        &'r str: Decode<'r, DB>,
+    {
+        #[inline]
+        fn decode(value: DB::ValueRef<'r>) -> Result<Self, BoxDynError> {
+            Ok(Symbol::try_new(<&str as Decode<DB>>::decode(value)?)?)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "diesel")]
+    #[test]
+    fn diesel() {
+        use ::diesel::prelude::*;
+
+        use crate::Symbol;
+
+        ::diesel::table! {
+            items (id) {
+                id -> Integer,
+                name -> Text,
+                tag -> Nullable<Text>,
+            }
+        }
+
+        #[derive(Debug, PartialEq, Insertable, Queryable)]
+        #[diesel(table_name = items)]
+        struct Item {
+            id: i32,
+            name: Symbol,
+            tag: Option<Symbol>,
+        }
+
+        let mut connection = SqliteConnection::establish(":memory:").unwrap();
+        ::diesel::sql_query(
+            "CREATE TABLE items (id INTEGER PRIMARY KEY, name TEXT NOT NULL, tag TEXT)",
+        )
+        .execute(&mut connection)
+        .unwrap();
+        let item = Item {
+            id: 1,
+            name: Symbol::new("database-diesel-foo"),
+            tag: Some(Symbol::new("database-diesel-bar")),
+        };
+        ::diesel::insert_into(items::table)
+            .values(&item)
+            .execute(&mut connection)
+            .unwrap();
+        let other: Item = items::table
+            .filter(items::name.eq(item.name))
+            .first(&mut connection)
+            .unwrap();
+        assert_eq!(other, item);
+    }
+
+    #[cfg(feature = "sqlx")]
+    #[test]
+    fn sqlx() {
+        use ::sqlx::{Connection, SqliteConnection};
+
+        use crate::Symbol;
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            let mut connection = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+            let one = Symbol::new("database-sqlx");
+            let other: Symbol = ::sqlx::query_scalar("SELECT ?")
+                .bind(one)
+                .fetch_one(&mut connection)
+                .await
+                .unwrap();
+            assert_eq!(other, one);
+        });
+    }
+}
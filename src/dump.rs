@@ -0,0 +1,247 @@
+//! Export and import of the storage.
+
+use std::io::{Error, ErrorKind, Read, Result, Write};
+
+use crate::{InternError, RemapTable, Symbol};
+
+const MAGIC: [u8; 4] = *b"box\0";
+const VERSION: u32 = 1;
+
+/// A format of `dump`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    /// One string per line, with line `i` holding the string with identifier
+    /// `i`, which rejects strings containing line feeds.
+    #[default]
+    Lines,
+    /// A header with a magic number, a version, and the number of strings
+    /// followed by the strings in the order of their identifiers, each
+    /// prefixed with its length, with all integers in little endian.
+    Binary,
+}
+
+/// A report of `preload`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Report {
+    /// The number of strings that have been stored.
+    pub added: usize,
+    /// The number of strings that had already been stored.
+    pub present: usize,
+}
+
+/// Write all strings stored so far in the order of their identifiers.
+///
+/// The output can be restored via `preload` or translated via `import` in
+/// another process. The writer is used as is, and buffering it is up to the
+/// caller.
+pub fn dump<W: Write>(mut writer: W, format: Format) -> Result<()> {
+    let values = Symbol::all_strs().collect::<Vec<_>>();
+    match format {
+        Format::Lines => {
+            for value in values {
+                if value.contains('\n') {
+                    let message = "the string contains a line feed";
+                    return Err(Error::new(ErrorKind::InvalidData, message));
+                }
+                writer.write_all(value.as_bytes())?;
+                writer.write_all(b"\n")?;
+            }
+        }
+        Format::Binary => {
+            writer.write_all(&MAGIC)?;
+            writer.write_all(&VERSION.to_le_bytes())?;
+            writer.write_all(&(values.len() as u64).to_le_bytes())?;
+            for value in values {
+                writer.write_all(&(value.len() as u64).to_le_bytes())?;
+                writer.write_all(value.as_bytes())?;
+            }
+        }
+    }
+    writer.flush()
+}
+
+/// Store all strings written via `dump` in either format.
+///
+/// All locks are acquired once for the whole batch, and restoring a dump at
+/// startup gives the strings the same identifiers as in the dumped process.
+pub fn preload<R: Read>(mut reader: R) -> Result<Report> {
+    let mut buffer = Vec::new();
+    reader.read_to_end(&mut buffer)?;
+    let values = parse(&buffer)?;
+    let count = Symbol::count();
+    report(count, Symbol::try_new_all(values))
+}
+
+/// Store all strings written via `dump` in another process and return the
+/// mapping from their identifiers there to those here.
+///
+/// The mapping translates identifiers embedded in data shared between the
+/// processes via `Symbol::remap`.
+pub fn import<R: Read>(mut reader: R) -> Result<RemapTable> {
+    let mut buffer = Vec::new();
+    reader.read_to_end(&mut buffer)?;
+    let symbols = Symbol::try_new_all(parse(&buffer)?).map_err(Error::other)?;
+    Ok(RemapTable::from(
+        symbols.iter().map(Symbol::id).collect::<Vec<_>>(),
+    ))
+}
+
+/// Store all strings of a file written via `dump` in either format without
+/// copying them.
+///
+/// The file is mapped into memory for the rest of the process, and the
+/// strings are served from the mapping, while new strings are stored as
+/// usual.
+///
+/// # Safety
+///
+/// The file must not be modified or truncated for the rest of the process.
+#[cfg(feature = "mmap")]
+pub unsafe fn preload_mapped(file: &std::fs::File) -> Result<Report> {
+    // SAFETY: The caller guarantees that the file stays intact.
+    let mapping = unsafe { memmap2::Mmap::map(file)? };
+    let buffer: &'static [u8] = alloc::boxed::Box::leak(alloc::boxed::Box::new(mapping));
+    let values = parse(buffer)?;
+    let count = Symbol::count();
+    report(count, Symbol::try_new_all_static(values))
+}
+
+fn parse(buffer: &[u8]) -> Result<Vec<&str>> {
+    if buffer.starts_with(&MAGIC) {
+        return decode(buffer);
+    }
+    let values = core::str::from_utf8(buffer)
+        .map_err(|error| Error::new(ErrorKind::InvalidData, error))?
+        .split_terminator('\n')
+        .collect();
+    Ok(values)
+}
+
+fn report(count: usize, symbols: core::result::Result<Vec<Symbol>, InternError>) -> Result<Report> {
+    let symbols = symbols.map_err(Error::other)?;
+    let added = symbols.iter().filter(|symbol| symbol.id() >= count).count();
+    Ok(Report {
+        added,
+        present: symbols.len() - added,
+    })
+}
+
+fn decode(buffer: &[u8]) -> Result<Vec<&str>> {
+    fn take<'l>(buffer: &mut &'l [u8], length: usize) -> Result<&'l [u8]> {
+        if buffer.len() < length {
+            return Err(Error::from(ErrorKind::UnexpectedEof));
+        }
+        let (head, tail) = buffer.split_at(length);
+        *buffer = tail;
+        Ok(head)
+    }
+
+    fn number(buffer: &mut &[u8]) -> Result<usize> {
+        let value = u64::from_le_bytes(take(buffer, 8)?.try_into().unwrap());
+        usize::try_from(value).map_err(|error| Error::new(ErrorKind::InvalidData, error))
+    }
+
+    let mut buffer = &buffer[MAGIC.len()..];
+    let version = u32::from_le_bytes(take(&mut buffer, 4)?.try_into().unwrap());
+    if version != VERSION {
+        let message = "the version is not supported";
+        return Err(Error::new(ErrorKind::InvalidData, message));
+    }
+    let count = number(&mut buffer)?;
+    let mut values = Vec::with_capacity(count.min(buffer.len() / 8));
+    for _ in 0..count {
+        let length = number(&mut buffer)?;
+        let value = core::str::from_utf8(take(&mut buffer, length)?)
+            .map_err(|error| Error::new(ErrorKind::InvalidData, error))?;
+        values.push(value);
+    }
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Format, Report};
+    use crate::Symbol;
+
+    #[test]
+    fn dump() {
+        let one = Symbol::new("dump-foo");
+        let mut buffer = Vec::new();
+        super::dump(&mut buffer, Format::Lines).unwrap();
+        let value = String::from_utf8(buffer).unwrap();
+        assert!(value.starts_with('\n'));
+        assert_eq!(value.lines().nth(one.id()), Some("dump-foo"));
+
+        let mut buffer = Vec::new();
+        super::dump(&mut buffer, Format::Binary).unwrap();
+        assert_eq!(&buffer[..8], b"box\0\x01\0\0\0");
+        let count = u64::from_le_bytes(buffer[8..16].try_into().unwrap());
+        assert!(count as usize > one.id());
+        assert_eq!(&buffer[16..24], &[0; 8]);
+
+        Symbol::new("dump\nbar");
+        assert!(super::dump(&mut Vec::new(), Format::Lines).is_err());
+    }
+
+    #[test]
+    fn import() {
+        let one = Symbol::new("import-foo");
+        let table = super::import(&b"\nimport-bar\nimport-foo\n"[..]).unwrap();
+        assert_eq!(table.len(), 3);
+        assert_eq!(table.get(0), Some(0));
+        assert_eq!(table.get(2), Some(one.id()));
+        let other = Symbol::from_id(table.get(1).unwrap()).unwrap();
+        assert_eq!(other, "import-bar");
+        assert_eq!(Symbol::from_id(2).unwrap().remap(&table), Some(one));
+    }
+
+    #[test]
+    fn preload() {
+        let one = Symbol::new("preload-foo");
+        let value = "preload-foo\npreload-bar\n";
+        let report = super::preload(value.as_bytes()).unwrap();
+        assert_eq!(
+            report,
+            Report {
+                added: 1,
+                present: 1
+            }
+        );
+        assert_eq!(Symbol::get("preload-foo"), Some(one));
+        assert!(Symbol::is_interned("preload-bar"));
+
+        let mut buffer = Vec::new();
+        super::dump(&mut buffer, Format::Binary).unwrap();
+        let report = super::preload(&buffer[..]).unwrap();
+        assert_eq!(report.added, 0);
+        assert!(report.present > one.id());
+        assert!(super::preload(&buffer[..buffer.len() - 1]).is_err());
+        buffer[4] = 2;
+        assert!(super::preload(&buffer[..]).is_err());
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn preload_mapped() {
+        use std::io::Write;
+
+        let path = std::env::temp_dir().join(format!("box-{}", std::process::id()));
+        let mut file = std::fs::File::create(&path).unwrap();
+        file.write_all(b"mapped-foo\nmapped-bar\n").unwrap();
+        drop(file);
+        let file = std::fs::File::open(&path).unwrap();
+        // SAFETY: The file is not modified.
+        let report = unsafe { super::preload_mapped(&file) }.unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            report,
+            Report {
+                added: 2,
+                present: 0
+            }
+        );
+        let one = Symbol::get("mapped-bar").unwrap();
+        assert_eq!(one, "mapped-bar");
+        assert_eq!(Symbol::new("mapped-bar").as_ptr(), one.as_ptr());
+    }
+}
@@ -0,0 +1,23 @@
+use defmt::{Format, Formatter};
+
+use crate::Symbol;
+
+// Only the identifier is transmitted, which can be resolved on the host via
+// the output of `dump`.
+impl Format for Symbol {
+    #[inline]
+    fn format(&self, formatter: Formatter<'_>) {
+        defmt::write!(formatter, "Symbol({=usize})", self.id());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn format() {
+        fn check<T: defmt::Format>() {}
+
+        check::<crate::Symbol>();
+        check::<Option<crate::Symbol>>();
+    }
+}
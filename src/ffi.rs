@@ -0,0 +1,88 @@
+//! Functions for C and other languages.
+//!
+//! Symbols are passed around as identifiers, and strings as pointers to UTF-8
+//! bytes together with their lengths in bytes. The strings are not
+//! null-terminated, and they stay valid for the rest of the process.
+
+use crate::Symbol;
+
+/// The identifier returned when a string cannot be stored.
+pub const BOX_INVALID: u64 = u64::MAX;
+
+/// Store a string and return the identifier of its symbol.
+///
+/// `BOX_INVALID` is returned if the bytes are not valid UTF-8 or if the
+/// storage refuses the string.
+///
+/// # Safety
+///
+/// The pointer must be valid for reading `len` bytes, unless `len` is zero,
+/// in which case it may be null.
+#[no_mangle]
+pub unsafe extern "C" fn box_intern(ptr: *const u8, len: usize) -> u64 {
+    if len == 0 {
+        return Symbol::EMPTY.id() as u64;
+    }
+    // SAFETY: The caller guarantees that the pointer is valid for the length.
+    let bytes = unsafe { core::slice::from_raw_parts(ptr, len) };
+    core::str::from_utf8(bytes)
+        .ok()
+        .and_then(|value| Symbol::try_new(value).ok())
+        .map_or(BOX_INVALID, |symbol| symbol.id() as u64)
+}
+
+/// Look up the string of a symbol given its identifier.
+///
+/// On success, the pointer and the length are written, and `true` is
+/// returned. Otherwise, nothing is written, and `false` is returned.
+///
+/// # Safety
+///
+/// The pointers must be valid for writing.
+#[no_mangle]
+pub unsafe extern "C" fn box_resolve(id: u64, ptr: *mut *const u8, len: *mut usize) -> bool {
+    let Some(symbol) = usize::try_from(id).ok().and_then(Symbol::from_id) else {
+        return false;
+    };
+    let value = symbol.as_static_str();
+    // SAFETY: The caller guarantees that the pointers are valid for writing.
+    unsafe {
+        ptr.write(value.as_ptr());
+        len.write(value.len());
+    }
+    true
+}
+
+/// Return the number of symbols.
+#[no_mangle]
+pub extern "C" fn box_count() -> u64 {
+    Symbol::count() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{box_count, box_intern, box_resolve, BOX_INVALID};
+
+    #[test]
+    fn ffi() {
+        let value = "ffi-foo";
+        // SAFETY: The pointer is valid for the length.
+        let id = unsafe { box_intern(value.as_ptr(), value.len()) };
+        assert_ne!(id, BOX_INVALID);
+        assert!(id < box_count());
+        // SAFETY: The pointer is valid for the length.
+        assert_eq!(unsafe { box_intern(value.as_ptr(), value.len()) }, id);
+        // SAFETY: The pointer may be null for a zero length.
+        assert_eq!(unsafe { box_intern(core::ptr::null(), 0) }, 0);
+        // SAFETY: The pointer is valid for the length.
+        assert_eq!(unsafe { box_intern([0xff].as_ptr(), 1) }, BOX_INVALID);
+
+        let (mut ptr, mut len) = (core::ptr::null(), 0);
+        // SAFETY: The pointers are valid for writing.
+        assert!(unsafe { box_resolve(id, &mut ptr, &mut len) });
+        // SAFETY: The pointer is valid for the length.
+        assert_eq!(unsafe { core::slice::from_raw_parts(ptr, len) }, b"ffi-foo");
+        // SAFETY: The pointers are valid for writing.
+        assert!(!unsafe { box_resolve(BOX_INVALID, &mut ptr, &mut len) });
+    }
+}
@@ -0,0 +1,218 @@
+//! Storage with shared prefixes.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+use hashbrown::HashTable;
+
+use crate::Hasher;
+
+/// A storage for hierarchical strings with shared prefixes.
+///
+/// Strings are split into segments by a separator, and each segment is stored
+/// once per distinct prefix, so that `service.region.host` and
+/// `service.region.port` share the storage of `service.region`. Resolution
+/// walks the prefixes and assembles the string, which trades a little speed
+/// for less memory when strings share long prefixes.
+///
+/// ## Example
+///
+/// ```
+/// use r#box::front::FrontCoded;
+///
+/// let mut storage = FrontCoded::new('.');
+/// let one = storage.intern("service.region.host");
+/// let other = storage.intern("service.region.port");
+/// assert_ne!(one, other);
+/// assert_eq!(storage.intern("service.region.host"), one);
+/// assert_eq!(storage.resolve(other).unwrap(), "service.region.port");
+/// ```
+pub struct FrontCoded {
+    separator: char,
+    nodes: Vec<Node>,
+    bytes: String,
+    map: HashTable<usize>,
+    hasher: Hasher,
+    count: usize,
+}
+
+struct Node {
+    parent: Option<usize>,
+    start: usize,
+    end: usize,
+    terminal: bool,
+}
+
+impl FrontCoded {
+    /// Create an empty instance splitting strings by a separator.
+    pub fn new(separator: char) -> Self {
+        Self {
+            separator,
+            nodes: Default::default(),
+            bytes: Default::default(),
+            map: Default::default(),
+            hasher: Default::default(),
+            count: 0,
+        }
+    }
+
+    /// Store a string unless already stored and return its identifier.
+    pub fn intern(&mut self, value: &str) -> usize {
+        let mut parent = None;
+        for segment in value.split(self.separator) {
+            let hash = self.hasher.hash_one((parent, segment));
+            let node = match self.find(hash, parent, segment) {
+                Some(node) => node,
+                _ => self.insert(hash, parent, segment),
+            };
+            parent = Some(node);
+        }
+        let node = parent.unwrap();
+        if !self.nodes[node].terminal {
+            self.nodes[node].terminal = true;
+            self.count += 1;
+        }
+        node
+    }
+
+    /// Find the identifier of a string without storing it.
+    pub fn get(&self, value: &str) -> Option<usize> {
+        let mut parent = None;
+        for segment in value.split(self.separator) {
+            let hash = self.hasher.hash_one((parent, segment));
+            parent = Some(self.find(hash, parent, segment)?);
+        }
+        parent.filter(|&node| self.nodes[node].terminal)
+    }
+
+    /// Assemble a string by its identifier.
+    pub fn resolve(&self, id: usize) -> Option<String> {
+        let mut value = String::new();
+        self.resolve_into(id, &mut value).then_some(value)
+    }
+
+    /// Assemble a string by its identifier into a buffer, which is cleared
+    /// first, and return whether the identifier is valid.
+    pub fn resolve_into(&self, id: usize, buffer: &mut String) -> bool {
+        buffer.clear();
+        if !self.nodes.get(id).is_some_and(|node| node.terminal) {
+            return false;
+        }
+        // The parents are walked twice, once to measure the string and once to
+        // fill it from the end, so that no list of segments is allocated.
+        let mut separator = [0; 4];
+        let separator = self.separator.encode_utf8(&mut separator).as_bytes();
+        let mut length = 0;
+        let mut node = Some(id);
+        while let Some(id) = node {
+            length += self.segment(id).len() + separator.len();
+            node = self.nodes[id].parent;
+        }
+        let mut bytes = core::mem::take(buffer).into_bytes();
+        bytes.resize(length - separator.len(), 0);
+        let mut end = bytes.len();
+        let mut node = Some(id);
+        while let Some(id) = node {
+            let segment = self.segment(id).as_bytes();
+            bytes[end - segment.len()..end].copy_from_slice(segment);
+            end -= segment.len();
+            node = self.nodes[id].parent;
+            if node.is_some() {
+                bytes[end - separator.len()..end].copy_from_slice(separator);
+                end -= separator.len();
+            }
+        }
+        // SAFETY: The bytes have been copied from segments and separators.
+        *buffer = unsafe { String::from_utf8_unchecked(bytes) };
+        true
+    }
+
+    /// Return the number of strings stored.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Check if no strings are stored.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Return the number of bytes occupied by the stored segments.
+    #[inline]
+    pub fn bytes(&self) -> usize {
+        self.bytes.len()
+    }
+
+    fn find(&self, hash: u64, parent: Option<usize>, segment: &str) -> Option<usize> {
+        let eq = |&node: &usize| self.nodes[node].parent == parent && self.segment(node) == segment;
+        self.map.find(hash, eq).copied()
+    }
+
+    fn insert(&mut self, hash: u64, parent: Option<usize>, segment: &str) -> usize {
+        let node = self.nodes.len();
+        let start = self.bytes.len();
+        self.bytes.push_str(segment);
+        self.nodes.push(Node {
+            parent,
+            start,
+            end: self.bytes.len(),
+            terminal: false,
+        });
+        let Self {
+            nodes,
+            bytes,
+            hasher,
+            ..
+        } = self;
+        self.map.insert_unique(hash, node, |&node| {
+            let Node {
+                parent, start, end, ..
+            } = nodes[node];
+            hasher.hash_one((parent, &bytes[start..end]))
+        });
+        node
+    }
+
+    #[inline]
+    fn segment(&self, node: usize) -> &str {
+        let Node { start, end, .. } = self.nodes[node];
+        &self.bytes[start..end]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FrontCoded;
+
+    #[test]
+    fn intern() {
+        let mut storage = FrontCoded::new('.');
+        let one = storage.intern("a.b.c");
+        let other = storage.intern("a.b.d");
+        assert_eq!(storage.len(), 2);
+        assert_eq!(storage.bytes(), 4);
+        assert_eq!(storage.get("a.b.c"), Some(one));
+        assert_eq!(storage.get("a.b"), None);
+        assert_eq!(storage.resolve(other).unwrap(), "a.b.d");
+        let one = storage.intern("a.b");
+        assert_eq!(storage.resolve(one).unwrap(), "a.b");
+        let one = storage.intern("");
+        assert_eq!(storage.resolve(one).unwrap(), "");
+        assert_eq!(storage.len(), 4);
+    }
+
+    #[test]
+    fn resolve_into() {
+        let mut storage = FrontCoded::new('→');
+        let one = storage.intern("a→bc→d");
+        let mut buffer = String::from("previous");
+        assert!(storage.resolve_into(one, &mut buffer));
+        assert_eq!(buffer, "a→bc→d");
+        let capacity = buffer.capacity();
+        assert!(!storage.resolve_into(one + 1, &mut buffer));
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.capacity(), capacity);
+    }
+}
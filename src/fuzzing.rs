@@ -0,0 +1,48 @@
+use arbitrary::{Arbitrary, Error, Result, Unstructured};
+
+use crate::Symbol;
+
+// The maximum length of generated strings, which keeps the storage, whose
+// memory is never reclaimed, from growing quickly while fuzzing.
+const LENGTH: usize = 64;
+
+impl<'l> Arbitrary<'l> for Symbol {
+    fn arbitrary(unstructured: &mut Unstructured<'l>) -> Result<Self> {
+        intern(<&str>::arbitrary(unstructured)?)
+    }
+
+    fn arbitrary_take_rest(unstructured: Unstructured<'l>) -> Result<Self> {
+        intern(<&str>::arbitrary_take_rest(unstructured)?)
+    }
+
+    #[inline]
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        <&str>::size_hint(depth)
+    }
+}
+
+fn intern(value: &str) -> Result<Symbol> {
+    let mut end = value.len().min(LENGTH);
+    while !value.is_char_boundary(end) {
+        end -= 1;
+    }
+    Symbol::try_new(&value[..end]).map_err(|_| Error::IncorrectFormat)
+}
+
+#[cfg(test)]
+mod tests {
+    use arbitrary::{Arbitrary, Unstructured};
+
+    use crate::Symbol;
+
+    #[test]
+    fn arbitrary() {
+        let bytes = "é".repeat(100).into_bytes();
+        let symbol = Symbol::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+        assert!(symbol.len() <= super::LENGTH);
+        let symbol = Symbol::arbitrary_take_rest(Unstructured::new(&bytes)).unwrap();
+        assert_eq!(symbol, "é".repeat(32));
+        let symbol = Symbol::arbitrary_take_rest(Unstructured::new(&[])).unwrap();
+        assert_eq!(symbol, Symbol::EMPTY);
+    }
+}
@@ -0,0 +1,132 @@
+use core::hash::{BuildHasherDefault, Hasher};
+
+/// A hash map with symbols as keys.
+#[cfg(feature = "std")]
+pub type SymbolHashMap<V> = std::collections::HashMap<crate::Symbol, V, SymbolBuildHasher>;
+
+/// A hash set of symbols.
+#[cfg(feature = "std")]
+pub type SymbolHashSet = std::collections::HashSet<crate::Symbol, SymbolBuildHasher>;
+
+/// A hash map with symbols as keys hashed by their identifiers.
+#[cfg(feature = "std")]
+pub type SymbolIdMap<V> = std::collections::HashMap<SymbolId, V, SymbolIdBuildHasher>;
+
+/// A hash set of symbols hashed by their identifiers.
+#[cfg(feature = "std")]
+pub type SymbolIdSet = std::collections::HashSet<SymbolId, SymbolIdBuildHasher>;
+
+/// An insertion-ordered map with symbols as keys.
+#[cfg(feature = "indexmap")]
+pub type SymbolIndexMap<V> = indexmap::IndexMap<crate::Symbol, V, SymbolBuildHasher>;
+
+/// An insertion-ordered set of symbols.
+#[cfg(feature = "indexmap")]
+pub type SymbolIndexSet = indexmap::IndexSet<crate::Symbol, SymbolBuildHasher>;
+
+/// A builder of `SymbolHasher`.
+pub type SymbolBuildHasher = BuildHasherDefault<SymbolHasher>;
+
+/// A builder of `SymbolIdHasher`.
+pub type SymbolIdBuildHasher = BuildHasherDefault<SymbolIdHasher>;
+
+/// A symbol hashed by its identifier.
+///
+/// Unlike `Symbol`, the key does not implement `Borrow<str>`, so its hash does
+/// not have to agree with the one of the underlying string, and maps keyed by
+/// it cannot be looked up by strings.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SymbolId(pub crate::Symbol);
+
+/// A pass-through hasher for `SymbolId`.
+///
+/// The identifier is only multiplied by a constant, so that the bits used by
+/// hash tables to select buckets are spread.
+#[derive(Clone, Copy, Default)]
+pub struct SymbolIdHasher(u64);
+
+/// A fast non-cryptographic hasher for symbols.
+///
+/// Since hashing of symbols is consistent with `Borrow<str>`, the hasher
+/// processes the underlying strings, which are typically short, with a few
+/// multiplications per eight bytes instead of SipHash.
+/// An identity hasher is impossible for `Symbol` itself, which is why
+/// `SymbolIdMap` keys symbols wrapped in `SymbolId` instead.
+#[derive(Clone, Copy, Default)]
+pub struct SymbolHasher(u64);
+
+const SEED: u64 = 0xf135_7aea_2e62_a9c5;
+
+impl SymbolHasher {
+    #[inline]
+    fn add(&mut self, word: u64) {
+        self.0 = (self.0 ^ word).wrapping_mul(SEED);
+    }
+}
+
+impl Hasher for SymbolHasher {
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.0.rotate_left(26)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            self.add(u64::from_le_bytes(chunk.try_into().unwrap()));
+        }
+        let remainder = chunks.remainder();
+        if !remainder.is_empty() {
+            let mut buffer = [0; 8];
+            buffer[..remainder.len()].copy_from_slice(remainder);
+            self.add(u64::from_le_bytes(buffer) ^ remainder.len() as u64);
+        }
+    }
+
+    #[inline]
+    fn write_u8(&mut self, value: u8) {
+        self.add(value as u64);
+    }
+
+    #[inline]
+    fn write_u64(&mut self, value: u64) {
+        self.add(value);
+    }
+
+    #[inline]
+    fn write_usize(&mut self, value: usize) {
+        self.add(value as u64);
+    }
+}
+
+impl From<crate::Symbol> for SymbolId {
+    #[inline]
+    fn from(symbol: crate::Symbol) -> Self {
+        Self(symbol)
+    }
+}
+
+impl core::hash::Hash for SymbolId {
+    #[inline]
+    fn hash<T: Hasher>(&self, state: &mut T) {
+        state.write_usize(self.0.id())
+    }
+}
+
+impl Hasher for SymbolIdHasher {
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.0.wrapping_mul(SEED)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = self.0.rotate_left(8) ^ byte as u64;
+        }
+    }
+
+    #[inline]
+    fn write_usize(&mut self, value: usize) {
+        self.0 = value as u64;
+    }
+}
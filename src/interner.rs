@@ -0,0 +1,182 @@
+//! Storage owned by its user.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+use hashbrown::HashTable;
+
+use crate::Hasher;
+
+/// A storage for unique strings owned by its user.
+///
+/// Unlike `Symbol`, which refers to a process-wide storage whose strings live
+/// until the process exits, an instance owns its strings and releases them
+/// once dropped, which suits pools per document or per compilation unit. The
+/// storage behind `Symbol` is not built on top of this type, since it is
+/// sharded, resolves identifiers without locking, and hands out static
+/// strings, none of which an owned storage needs. The strings of an instance
+/// can be moved over via `Symbol::absorb`.
+///
+/// ## Example
+///
+/// ```
+/// use r#box::Interner;
+///
+/// let mut interner = Interner::new();
+/// let one = interner.intern("foo");
+/// let other = interner.intern("bar");
+/// assert_ne!(one, other);
+/// assert_eq!(interner.intern("foo"), one);
+/// assert_eq!(interner.resolve(other), Some("bar"));
+/// ```
+#[derive(Default)]
+pub struct Interner {
+    spans: Vec<(usize, usize)>,
+    bytes: String,
+    map: HashTable<usize>,
+    hasher: Hasher,
+}
+
+/// A mapping from the identifiers of an absorbed storage to those of the
+/// absorbing one.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RemapTable(Vec<usize>);
+
+impl Interner {
+    /// Create an empty instance.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store a string unless already stored and return its identifier.
+    pub fn intern(&mut self, value: &str) -> usize {
+        let hash = self.hasher.hash_one(value);
+        if let Some(id) = self.find(hash, value) {
+            return id;
+        }
+        let id = self.spans.len();
+        let start = self.bytes.len();
+        self.bytes.push_str(value);
+        self.spans.push((start, self.bytes.len()));
+        let Self {
+            spans,
+            bytes,
+            hasher,
+            ..
+        } = self;
+        self.map.insert_unique(hash, id, |&id| {
+            let (start, end) = spans[id];
+            hasher.hash_one(&bytes[start..end])
+        });
+        id
+    }
+
+    /// Find the identifier of a string without storing it.
+    #[inline]
+    pub fn get(&self, value: &str) -> Option<usize> {
+        self.find(self.hasher.hash_one(value), value)
+    }
+
+    /// Return the string by its identifier.
+    #[inline]
+    pub fn resolve(&self, id: usize) -> Option<&str> {
+        let &(start, end) = self.spans.get(id)?;
+        Some(&self.bytes[start..end])
+    }
+
+    /// Return the number of strings stored.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    /// Check if no strings are stored.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    /// Store the strings of another instance and return the mapping from their
+    /// identifiers there to those here.
+    pub fn absorb(&mut self, other: Interner) -> RemapTable {
+        RemapTable(other.iter().map(|value| self.intern(value)).collect())
+    }
+
+    /// Iterate over the stored strings in the order of their identifiers.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.spans
+            .iter()
+            .map(|&(start, end)| &self.bytes[start..end])
+    }
+
+    fn find(&self, hash: u64, value: &str) -> Option<usize> {
+        let eq = |&id: &usize| self.resolve(id) == Some(value);
+        self.map.find(hash, eq).copied()
+    }
+}
+
+impl RemapTable {
+    /// Return the identifier corresponding to an identifier of the absorbed
+    /// storage.
+    #[inline]
+    pub fn get(&self, id: usize) -> Option<usize> {
+        self.0.get(id).copied()
+    }
+
+    /// Return the number of identifiers.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Check if there are no identifiers.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<Vec<usize>> for RemapTable {
+    #[inline]
+    fn from(ids: Vec<usize>) -> Self {
+        Self(ids)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Interner;
+
+    #[test]
+    fn intern() {
+        let mut interner = Interner::new();
+        assert!(interner.is_empty());
+        let one = interner.intern("foo");
+        let other = interner.intern("bar");
+        assert_eq!(interner.intern("foo"), one);
+        assert_eq!(interner.len(), 2);
+        assert_eq!(interner.get("bar"), Some(other));
+        assert_eq!(interner.get("baz"), None);
+        assert_eq!(interner.resolve(one), Some("foo"));
+        assert_eq!(interner.resolve(2), None);
+        let empty = interner.intern("");
+        assert_eq!(interner.resolve(empty), Some(""));
+        assert_eq!(interner.iter().collect::<Vec<_>>(), ["foo", "bar", ""]);
+    }
+
+    #[test]
+    fn absorb() {
+        let mut interner = Interner::new();
+        interner.intern("foo");
+        let mut other = Interner::new();
+        let bar = other.intern("bar");
+        let foo = other.intern("foo");
+        let table = interner.absorb(other);
+        assert_eq!(table.len(), 2);
+        assert_eq!(table.get(foo), Some(0));
+        assert_eq!(table.get(bar), Some(1));
+        assert_eq!(table.get(2), None);
+        assert_eq!(interner.resolve(1), Some("bar"));
+    }
+}
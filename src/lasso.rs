@@ -0,0 +1,81 @@
+//! Interoperability with lasso.
+//!
+//! A `Rodeo` can be used as a storage via `scoped_with`, so code written
+//! against this crate runs on top of an existing interner, and the strings of
+//! a `Rodeo` can be transplanted into symbols via `transplant`.
+
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+
+use ::lasso::{Key, Rodeo};
+
+use crate::{Backend, Symbol};
+
+impl<K, S> Backend for Rodeo<K, S>
+where
+    K: Key,
+    S: BuildHasher,
+{
+    #[inline]
+    fn intern(&mut self, value: &str) -> usize {
+        self.get_or_intern(value).into_usize()
+    }
+
+    #[inline]
+    fn get(&self, value: &str) -> Option<usize> {
+        Rodeo::get(self, value).map(Key::into_usize)
+    }
+
+    #[inline]
+    fn resolve(&self, id: usize) -> Option<Cow<'_, str>> {
+        self.try_resolve(&K::try_from_usize(id)?).map(Cow::Borrowed)
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        Rodeo::len(self)
+    }
+}
+
+/// Create a symbol for each string of an interner.
+///
+/// The symbol of a key is found at `key.into_usize()`.
+pub fn transplant<K, S>(rodeo: &Rodeo<K, S>) -> Vec<Symbol>
+where
+    K: Key,
+{
+    rodeo.strings().map(Symbol::new).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use lasso::{Key, Rodeo};
+
+    use crate::{scoped_with, Symbol};
+
+    #[test]
+    fn backend() {
+        let mut rodeo = Rodeo::default();
+        let key = rodeo.get_or_intern("lasso-foo");
+        scoped_with(rodeo, |scope| {
+            let one = scope.intern("lasso-foo");
+            assert_eq!(one.id(), key.into_usize());
+            let other = scope.intern("lasso-bar");
+            assert_eq!(scope.resolve(other), "lasso-bar");
+            assert_eq!(scope.len(), 2);
+        });
+    }
+
+    #[test]
+    fn transplant() {
+        use super::transplant;
+
+        let mut rodeo = Rodeo::default();
+        let one = rodeo.get_or_intern("lasso-baz");
+        let other = rodeo.get_or_intern(Symbol::new("lasso-qux"));
+        let symbols = transplant(&rodeo);
+        assert_eq!(symbols[one.into_usize()].as_static_str(), "lasso-baz");
+        assert_eq!(symbols[other.into_usize()], Symbol::new("lasso-qux"));
+    }
+}
@@ -0,0 +1,72 @@
+use core::ops::Deref;
+
+use crate::sync::OnceLock;
+use crate::Symbol;
+
+/// A symbol created on first use.
+///
+/// ## Example
+///
+/// ```
+/// use r#box::{LazySymbol, Symbol};
+///
+/// static FOO: LazySymbol = LazySymbol::new("foo");
+///
+/// assert_eq!(*FOO, Symbol::new("foo"));
+/// ```
+pub struct LazySymbol {
+    value: &'static str,
+    symbol: OnceLock<Symbol>,
+}
+
+impl LazySymbol {
+    /// Create an instance for a string.
+    #[inline]
+    pub const fn new(value: &'static str) -> Self {
+        Self {
+            value,
+            symbol: OnceLock::new(),
+        }
+    }
+
+    /// Return the symbol, creating it if needed.
+    ///
+    /// The function panics if the limit set via `limit` is reached or the
+    /// storage is frozen via `freeze` when the symbol is created.
+    #[inline]
+    pub fn get(&self) -> Symbol {
+        *self.deref()
+    }
+}
+
+impl Deref for LazySymbol {
+    type Target = Symbol;
+
+    #[inline]
+    fn deref(&self) -> &Symbol {
+        self.symbol.get_or_init(|| Symbol::new_static(self.value))
+    }
+}
+
+impl core::fmt::Debug for LazySymbol {
+    #[inline]
+    fn fmt(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+        self.value.fmt(formatter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LazySymbol;
+    use crate::Symbol;
+
+    #[test]
+    fn lazy() {
+        static FOO: LazySymbol = LazySymbol::new("lazy-foo");
+
+        assert!(!Symbol::is_interned("lazy-foo"));
+        assert_eq!(FOO.get(), "lazy-foo");
+        assert!(Symbol::is_interned("lazy-foo"));
+        assert_eq!(*FOO, Symbol::new("lazy-foo"));
+    }
+}
@@ -9,160 +9,2568 @@
 //! let other = Symbol::new("foo");
 //! assert_eq!(one.as_ptr(), other.as_ptr());
 //! ```
+//!
+//! ## Concurrency
+//!
+//! Strings are distributed over shards by their hashes, so that looking up and
+//! creating instances for different strings lock different shards. Creating an
+//! instance for a string that is not interned yet also takes a lock shared by
+//! all shards, which checks the limits, copies the string, and assigns the next
+//! identifier, so that identifiers stay dense. Concurrent creation of new
+//! instances is therefore serialized for the duration of the copy, which is
+//! measured by benchmark `miss_parallel` as opposed to `hit_parallel`.
+//!
+//! ## Features
+//!
+//! Feature `std`, which is enabled by default, can be disabled in order to use
+//! the crate without the standard library, in which case locks spin instead of
+//! parking threads, strings are hashed with a fixed seed unless feature
+//! `fast-hash` is enabled, and the cache per thread is unavailable. Locks also
+//! spin on WebAssembly with threads, where the main thread is not allowed to
+//! block.
+//!
+//! Feature `cstr` stores every string with a trailing nul byte, which makes
+//! `Symbol::as_cstr` available, in which case static strings are copied too.
+//!
+//! Feature `metrics` reports counter `symbols_created_total` along with gauges
+//! `pool_bytes` and `intern_hit_ratio` via the metrics facade.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+#![deny(unsafe_op_in_unsafe_fn)]
+#![warn(clippy::undocumented_unsafe_blocks)]
+
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+use core::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
+use hashbrown::hash_table::{Entry, HashTable, VacantEntry};
+use hashbrown::HashMap;
+
+use sync::{Mutex, OnceLock, RwLock};
+
+pub use backend::Backend;
+pub use bounded::{BoundedInterner, BoundedSymbol};
+pub use bytes::ByteSymbol;
+pub use cache::set_thread_cache_capacity;
+pub use hash::{SymbolBuildHasher, SymbolHasher, SymbolId, SymbolIdBuildHasher, SymbolIdHasher};
+pub use interner::{Interner, RemapTable};
+pub use lazy::LazySymbol;
+pub use pool::{Pool, PoolSymbol};
+pub use rc::RcSymbol;
+pub use scoped::{scoped, scoped_with, Scope, ScopedSymbol};
+pub use tagged::{Tag, TaggedSymbol};
+
+#[cfg(feature = "std")]
+pub use dump::{dump, import, preload, Format, Report};
+
+#[cfg(feature = "std")]
+pub use hash::{SymbolHashMap, SymbolHashSet, SymbolIdMap, SymbolIdSet};
+
+#[cfg(feature = "std")]
+pub use path::PathSymbol;
+
+#[cfg(feature = "mmap")]
+pub use dump::preload_mapped;
+
+#[cfg(feature = "indexmap")]
+pub use hash::{SymbolIndexMap, SymbolIndexSet};
+
+#[cfg(feature = "macros")]
+pub use box_macros::{include_symbols, FieldSymbols, SymbolEnum};
+
+#[cfg(feature = "debug-tracking")]
+pub use tracking::top_sites;
+
+#[cfg(feature = "icu")]
+pub use collation::SymbolCollator;
+
+#[cfg(feature = "local")]
+pub use local::LocalSymbol;
+
+#[cfg(feature = "pyo3")]
+pub use python::PySymbol;
+
+#[cfg(feature = "rayon")]
+pub use parallel::par_intern;
+
+#[cfg(feature = "rkyv")]
+pub use archive::{ArchivedSymbol, SymbolFlavor};
+
+const SHARDS: usize = 16;
+
+const ARENA: usize = 64 * 1024;
+
+// The bytes stored after each string.
+#[cfg(feature = "cstr")]
+const TERMINATOR: &str = "\0";
+#[cfg(not(feature = "cstr"))]
+const TERMINATOR: &str = "";
+
+#[cfg(all(feature = "std", not(feature = "fast-hash")))]
+type Hasher = std::collections::hash_map::RandomState;
+
+#[cfg(all(not(feature = "std"), not(feature = "fast-hash")))]
+type Hasher = SymbolBuildHasher;
+
+#[cfg(feature = "fast-hash")]
+type Hasher = rustc_hash::FxBuildHasher;
+
+const CHUNKS: usize = usize::BITS as usize - CHUNK_BITS;
+const CHUNK_BITS: usize = 5;
+
+const MIGRATION: usize = 8;
+
+static STATE: OnceLock<Box> = OnceLock::new();
+
+type Observer = alloc::sync::Arc<dyn Fn(&'static str, usize) + Send + Sync>;
+
+struct Box {
+    shards: [RwLock<Shard>; SHARDS],
+    table: Mutex<Table>,
+    values: Values,
+    frozen: OnceLock<Shard>,
+    saved: AtomicUsize,
+    lowercase: RwLock<HashMap<usize, Symbol, Hasher>>,
+    #[cfg(feature = "unicode-folding")]
+    folded: RwLock<HashMap<usize, Symbol, Hasher>>,
+    shadowed: RwLock<HashMap<&'static str, Symbol, Hasher>>,
+    observers: RwLock<alloc::sync::Arc<[Observer]>>,
+    revoked: RwLock<Vec<(usize, usize)>>,
+    config: Config,
+    folding: bool,
+    #[cfg(feature = "normalization")]
+    normalization: bool,
+    hasher: Hasher,
+}
+
+// A table that grows incrementally: once full, it is replaced with a larger
+// one, and the entries of the previous one are moved a few buckets at a time
+// with each insertion, so that no single insertion rehashes the whole table.
+#[derive(Default)]
+struct Shard {
+    map: HashTable<(&'static str, Symbol)>,
+    old: HashTable<(&'static str, Symbol)>,
+    cursor: usize,
+}
+
+struct Table {
+    arena: Arena,
+    bytes: usize,
+    limit: (usize, usize),
+    length: usize,
+}
+
+// A bump allocator over leaked chunks, which reduces the number of allocations
+// for short strings.
+#[derive(Default)]
+struct Arena {
+    chunk: &'static mut [u8],
+    allocated: usize,
+    used: usize,
+}
+
+// An append-only table readable without locking, in which chunk `i` has
+// `1 << (i + CHUNK_BITS)` slots, so that growing never moves existing slots.
+struct Values {
+    chunks: [OnceLock<alloc::boxed::Box<[Slot]>>; CHUNKS],
+    length: AtomicUsize,
+}
+
+// A slot that is valid when zeroed, so that chunks can be allocated without
+// initializing every slot.
+struct Slot {
+    pointer: AtomicPtr<u8>,
+    length: AtomicUsize,
+}
+
+/// An error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InternError {
+    /// The limit on the number of symbols or bytes is reached.
+    CapacityExceeded,
+    /// The storage is frozen.
+    Frozen,
+    /// The string is longer than the maximum set via `configure`.
+    TooLong,
+    /// The identifier does not refer to any symbol.
+    InvalidId,
+    /// The string already refers to another symbol.
+    Occupied,
+}
+
+/// A configuration of the storage.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Config {
+    /// The number of symbols to reserve capacity for.
+    pub capacity: usize,
+    /// The maximum number of symbols, including the empty one.
+    pub max_symbols: usize,
+    /// The maximum number of bytes in the underlying strings.
+    pub max_bytes: usize,
+    /// The maximum number of bytes in a single string.
+    pub max_length: usize,
+    /// Whether to convert strings to ASCII lowercase before interning.
+    pub case_folding: bool,
+    /// Whether to convert strings to Unicode Normalization Form C before
+    /// interning, so that canonically equivalent strings share a symbol.
+    #[cfg(feature = "normalization")]
+    pub normalization: bool,
+    /// The strings to intern upfront, so that the string at position `i`
+    /// receives identifier `i + 1` regardless of the order of interning
+    /// elsewhere, which makes the identifiers stable across processes. The
+    /// strings should be distinct and nonempty, since the empty string has
+    /// identifier zero.
+    pub reserved: &'static [&'static str],
+}
+
+/// An error returned when configuring a storage already in use.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ConfigureError;
+
+/// An error returned when creating a symbol from UTF-16.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FromUtf16Error {
+    /// The input is not valid UTF-16.
+    Decode(core::char::DecodeUtf16Error),
+    /// The string cannot be interned.
+    Intern(InternError),
+}
+
+/// A point in the history of the storage to roll back to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Checkpoint(usize);
+
+/// Statistics about the storage.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// The number of symbols, including the empty one.
+    pub symbols: usize,
+    /// The total number of bytes in the underlying strings.
+    pub bytes: usize,
+    /// The number of bytes occupied by the internal containers, including
+    /// their unused capacity.
+    pub overhead: usize,
+    /// The number of bytes that would have been allocated without
+    /// deduplication.
+    pub saved: usize,
+}
+
+/// A unique static string.
+///
+/// The identifier is stored as a non-zero 32-bit integer, so that
+/// `Option<Symbol>` occupies four bytes, unless feature `wide` is enabled, in
+/// which case a 64-bit integer is used.
+#[derive(Clone, Copy)]
+#[cfg_attr(
+    feature = "diesel",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow),
+    diesel(sql_type = diesel::sql_types::Text)
+)]
+pub struct Symbol(Raw);
+
+#[cfg(not(feature = "wide"))]
+type Raw = core::num::NonZeroU32;
+
+#[cfg(feature = "wide")]
+type Raw = core::num::NonZeroU64;
+
+impl Symbol {
+    /// The empty string.
+    pub const EMPTY: Self = Self(Raw::MIN);
+
+    /// Create a new instance.
+    ///
+    /// The function panics if the limit set via `limit` is reached or the
+    /// storage is frozen via `freeze`.
+    #[inline]
+    #[cfg_attr(feature = "debug-tracking", track_caller)]
+    pub fn new<T>(value: T) -> Self
+    where
+        T: AsRef<str> + Into<String>,
+    {
+        match Self::try_new(value) {
+            Ok(symbol) => symbol,
+            Err(error) => panic!("{error}"),
+        }
+    }
+
+    /// Create a new instance from a static string without copying it.
+    ///
+    /// The function panics if the limit set via `limit` is reached or the
+    /// storage is frozen via `freeze`.
+    #[inline]
+    #[cfg_attr(feature = "debug-tracking", track_caller)]
+    pub fn new_static(value: &'static str) -> Self {
+        match Self::try_new_static(value) {
+            Ok(symbol) => symbol,
+            Err(error) => panic!("{error}"),
+        }
+    }
+
+    /// Create a new instance unless the limit set via `limit` is reached or the
+    /// storage is frozen via `freeze`.
+    #[inline]
+    #[cfg_attr(feature = "debug-tracking", track_caller)]
+    pub fn try_new<T>(value: T) -> Result<Self, InternError>
+    where
+        T: AsRef<str> + Into<String>,
+    {
+        Self::intern(value, Arena::store)
+    }
+
+    /// Create a new instance from a static string without copying it unless the
+    /// limit set via `limit` is reached or the storage is frozen via `freeze`.
+    #[inline]
+    #[cfg_attr(feature = "debug-tracking", track_caller)]
+    pub fn try_new_static(value: &'static str) -> Result<Self, InternError> {
+        Self::intern(value, Arena::keep)
+    }
+
+    /// Create a new instance constructing the owned string only if `key` is
+    /// not interned yet.
+    ///
+    /// The string returned by `make` must be equal to `key`. The function
+    /// panics if the limit set via `limit` is reached or the storage is frozen
+    /// via `freeze`.
+    #[cfg_attr(feature = "debug-tracking", track_caller)]
+    pub fn new_with<F>(key: &str, make: F) -> Self
+    where
+        F: FnOnce() -> String,
+    {
+        match Self::try_new_with(key, make) {
+            Ok(symbol) => symbol,
+            Err(error) => panic!("{error}"),
+        }
+    }
+
+    /// Create a new instance constructing the owned string only if `key` is
+    /// not interned yet unless the limit set via `limit` is reached or the
+    /// storage is frozen via `freeze`.
+    ///
+    /// The string returned by `make` must be equal to `key`.
+    #[cfg_attr(feature = "debug-tracking", track_caller)]
+    pub fn try_new_with<F>(key: &str, make: F) -> Result<Self, InternError>
+    where
+        F: FnOnce() -> String,
+    {
+        let store = |arena: &mut Arena, key: &str| {
+            let value = make();
+            assert_eq!(value, key, "the constructed string differs from the key");
+            arena.store(value)
+        };
+        Self::intern(key, store)
+    }
+
+    /// Create a new instance from formatting arguments.
+    ///
+    /// The arguments are formatted into a reusable buffer, so that no string is
+    /// allocated if the result is already interned, unless feature `std` is
+    /// disabled. The function panics if the limit set via `limit` is reached or
+    /// the storage is frozen via `freeze`.
+    #[cfg_attr(feature = "debug-tracking", track_caller)]
+    pub fn from_fmt(arguments: core::fmt::Arguments<'_>) -> Self {
+        match Self::try_from_fmt(arguments) {
+            Ok(symbol) => symbol,
+            Err(error) => panic!("{error}"),
+        }
+    }
+
+    /// Create a new instance from formatting arguments unless the limit set via
+    /// `limit` is reached or the storage is frozen via `freeze`.
+    #[cfg_attr(feature = "debug-tracking", track_caller)]
+    pub fn try_from_fmt(arguments: core::fmt::Arguments<'_>) -> Result<Self, InternError> {
+        use core::fmt::Write;
+
+        if let Some(value) = arguments.as_str() {
+            return Self::try_new(value);
+        }
+        Self::with_buffer(|buffer| {
+            buffer
+                .write_fmt(arguments)
+                .expect("a formatting trait implementation returned an error");
+            Self::try_new(buffer.as_str())
+        })
+    }
+
+    /// Create a new instance from UTF-16.
+    ///
+    /// The function panics if the limit set via `limit` is reached or the
+    /// storage is frozen via `freeze`.
+    #[inline]
+    #[cfg_attr(feature = "debug-tracking", track_caller)]
+    pub fn from_utf16(value: &[u16]) -> Result<Self, core::char::DecodeUtf16Error> {
+        match Self::try_from_utf16(value) {
+            Ok(symbol) => Ok(symbol),
+            Err(FromUtf16Error::Decode(error)) => Err(error),
+            Err(FromUtf16Error::Intern(error)) => panic!("{error}"),
+        }
+    }
+
+    /// Create a new instance from UTF-16 unless the limit set via `limit` is
+    /// reached or the storage is frozen via `freeze`.
+    ///
+    /// The string is decoded into a reusable buffer, so that no string is
+    /// allocated if the result is already interned, unless feature `std` is
+    /// disabled.
+    #[cfg_attr(feature = "debug-tracking", track_caller)]
+    pub fn try_from_utf16(value: &[u16]) -> Result<Self, FromUtf16Error> {
+        Self::with_buffer(|buffer| {
+            for character in char::decode_utf16(value.iter().copied()) {
+                buffer.push(character?);
+            }
+            Ok(Self::try_new(buffer.as_str())?)
+        })
+    }
+
+    /// Create a new instance from UTF-8 replacing invalid sequences with
+    /// `U+FFFD`.
+    ///
+    /// The function panics if the limit set via `limit` is reached or the
+    /// storage is frozen via `freeze`.
+    #[cfg_attr(feature = "debug-tracking", track_caller)]
+    pub fn from_utf8_lossy(value: &[u8]) -> Self {
+        match Self::try_from_utf8_lossy(value) {
+            Ok(symbol) => symbol,
+            Err(error) => panic!("{error}"),
+        }
+    }
+
+    /// Create a new instance from UTF-8 replacing invalid sequences with
+    /// `U+FFFD` unless the limit set via `limit` is reached or the storage is
+    /// frozen via `freeze`.
+    ///
+    /// Invalid input is repaired in a reusable buffer, so that no string is
+    /// allocated if the result is already interned, unless feature `std` is
+    /// disabled.
+    #[cfg_attr(feature = "debug-tracking", track_caller)]
+    pub fn try_from_utf8_lossy(value: &[u8]) -> Result<Self, InternError> {
+        if let Ok(value) = core::str::from_utf8(value) {
+            return Self::try_new(value);
+        }
+        Self::with_buffer(|buffer| {
+            for chunk in value.utf8_chunks() {
+                buffer.push_str(chunk.valid());
+                if !chunk.invalid().is_empty() {
+                    buffer.push(char::REPLACEMENT_CHARACTER);
+                }
+            }
+            Self::try_new(buffer.as_str())
+        })
+    }
+
+    /// Create a new instance from the full Unicode case folding of a string.
+    ///
+    /// The function panics if the limit set via `limit` is reached or the
+    /// storage is frozen via `freeze`.
+    #[cfg(feature = "unicode-folding")]
+    #[cfg_attr(feature = "debug-tracking", track_caller)]
+    pub fn new_folded<T: AsRef<str>>(value: T) -> Self {
+        match Self::try_new_folded(value) {
+            Ok(symbol) => symbol,
+            Err(error) => panic!("{error}"),
+        }
+    }
+
+    /// Create a new instance from the full Unicode case folding of a string
+    /// unless the limit set via `limit` is reached or the storage is frozen via
+    /// `freeze`.
+    #[cfg(feature = "unicode-folding")]
+    #[cfg_attr(feature = "debug-tracking", track_caller)]
+    pub fn try_new_folded<T: AsRef<str>>(value: T) -> Result<Self, InternError> {
+        let value = value.as_ref();
+        if value
+            .bytes()
+            .all(|byte| byte.is_ascii() && !byte.is_ascii_uppercase())
+        {
+            return Self::try_new_exact(value.into());
+        }
+        Self::try_new_exact(caseless::default_case_fold_str(value))
+    }
+
+    // Run a function with an empty buffer reused across calls.
+    fn with_buffer<F, T>(function: F) -> T
+    where
+        F: FnOnce(&mut String) -> T,
+    {
+        #[cfg(feature = "std")]
+        thread_local! {
+            static BUFFER: core::cell::RefCell<String> = const {
+                core::cell::RefCell::new(String::new())
+            };
+        }
+
+        // The buffer is taken out for the duration of the call, since the
+        // function might intern symbols via the buffer itself.
+        #[cfg(feature = "std")]
+        let mut buffer = BUFFER.with(|buffer| buffer.take());
+        #[cfg(not(feature = "std"))]
+        let mut buffer = String::new();
+        buffer.clear();
+        let result = function(&mut buffer);
+        #[cfg(feature = "std")]
+        let _ = BUFFER.try_with(|slot| slot.replace(buffer));
+        result
+    }
+
+    #[cfg_attr(feature = "debug-tracking", track_caller)]
+    fn intern<T, F>(value: T, store: F) -> Result<Self, InternError>
+    where
+        T: AsRef<str>,
+        F: FnOnce(&mut Arena, T) -> &'static str,
+    {
+        if value.as_ref().is_empty() {
+            return Ok(Self::EMPTY);
+        }
+        let state = Box::instance();
+        if let Some(value) = state.fold(value.as_ref()) {
+            return Self::intern(value, Arena::store);
+        }
+        let hash = state.hasher.hash_one(value.as_ref());
+        let length = value.as_ref().len();
+        let epoch = cache::epoch();
+        if let Some(symbol) = cache::get(hash, value.as_ref(), epoch) {
+            return Ok(state.hit(symbol, length));
+        }
+        let symbol = state.intern(hash, value, store)?;
+        cache::put(hash, symbol, epoch);
+        Ok(symbol)
+    }
+
+    /// Create several new instances at once.
+    ///
+    /// All locks are acquired once for the whole batch. The function panics if
+    /// the limit set via `limit` is reached or the storage is frozen via
+    /// `freeze`.
+    #[cfg_attr(feature = "debug-tracking", track_caller)]
+    pub fn new_all<T>(values: T) -> Vec<Self>
+    where
+        T: IntoIterator,
+        T::Item: AsRef<str> + Into<String>,
+    {
+        match Self::try_new_all(values) {
+            Ok(symbols) => symbols,
+            Err(error) => panic!("{error}"),
+        }
+    }
+
+    /// Create several new instances at once unless the limit set via `limit`
+    /// is reached or the storage is frozen via `freeze`.
+    ///
+    /// The instances created before an error remain interned.
+    #[cfg_attr(feature = "debug-tracking", track_caller)]
+    pub fn try_new_all<T>(values: T) -> Result<Vec<Self>, InternError>
+    where
+        T: IntoIterator,
+        T::Item: AsRef<str> + Into<String>,
+    {
+        Self::intern_all(values, Arena::store)
+    }
+
+    #[cfg(feature = "mmap")]
+    #[cfg_attr(feature = "debug-tracking", track_caller)]
+    pub(crate) fn try_new_all_static<T>(values: T) -> Result<Vec<Self>, InternError>
+    where
+        T: IntoIterator<Item = &'static str>,
+    {
+        Self::intern_all(values, Arena::keep)
+    }
+
+    #[cfg_attr(feature = "debug-tracking", track_caller)]
+    fn intern_all<T, F>(values: T, store: F) -> Result<Vec<Self>, InternError>
+    where
+        T: IntoIterator,
+        T::Item: AsRef<str>,
+        F: Fn(&mut Arena, T::Item) -> &'static str,
+    {
+        let state = Box::instance();
+        if state.transforms() {
+            let values = values.into_iter();
+            return values.map(|value| Self::intern(value, &store)).collect();
+        }
+        let values = values.into_iter();
+        let (additional, _) = values.size_hint();
+        let mut shards = state.shards.each_ref().map(RwLock::write);
+        let mut table = state.table.lock();
+        state
+            .values
+            .reserve(state.values.len().saturating_add(additional));
+        let mut symbols = Vec::with_capacity(additional);
+        let start = state.values.len();
+        let mut result = Ok(());
+        for value in values {
+            let hash = state.hasher.hash_one(value.as_ref());
+            let shard = &mut shards[Box::locate(hash)];
+            let entry = shard.entry(hash, value.as_ref(), &state.hasher);
+            let symbol = match entry {
+                Ok(symbol) => Ok(state.hit(symbol, value.as_ref().len())),
+                Err(entry) => state.occupy(entry, &mut table, value, &store),
+            };
+            match symbol {
+                Ok(symbol) => symbols.push(symbol),
+                Err(error) => {
+                    result = Err(error);
+                    break;
+                }
+            }
+        }
+        drop((shards, table));
+        state.notify(start..state.values.len());
+        result.map(|_| symbols)
+    }
+
+    /// Create instances for the strings of a storage and return the mapping
+    /// from their identifiers there to those of the instances.
+    ///
+    /// The function panics if the limit set via `limit` is reached or the
+    /// storage is frozen via `freeze`.
+    pub fn absorb(other: &Interner) -> RemapTable {
+        let symbols = Self::new_all(other.iter());
+        RemapTable::from(symbols.iter().map(Self::id).collect::<Vec<_>>())
+    }
+
+    /// Find the instance corresponding to an instance of an absorbed storage.
+    ///
+    /// The identifier of the instance is interpreted as one of the storage
+    /// absorbed via `absorb`, which is the case for instances created via
+    /// `from_id` with identifiers from another process.
+    #[inline]
+    pub fn remap(&self, table: &RemapTable) -> Option<Self> {
+        Self::from_index(table.get(self.id())?)
+    }
+
+    /// Make a string resolve to an existing instance when interned.
+    ///
+    /// The string is subject to the same transformations and limits as in
+    /// `try_new`. Aliasing a string that already resolves to the instance has
+    /// no effect, and aliasing a string that resolves to another instance
+    /// fails, as does aliasing to an instance revoked via `rollback`.
+    ///
+    /// Aliases do not apply to the counterparts used for comparisons ignoring
+    /// the case, which always have the converted string.
+    pub fn alias(from: &str, to: Self) -> Result<(), InternError> {
+        let state = Box::instance();
+        if to.id() >= Self::count() || !to.is_valid() {
+            return Err(InternError::InvalidId);
+        }
+        if state.frozen.get().is_some() {
+            return Err(InternError::Frozen);
+        }
+        let folded = state.fold(from);
+        let from = folded.as_deref().unwrap_or(from);
+        if from.is_empty() {
+            if to == Self::EMPTY {
+                return Ok(());
+            }
+            return Err(InternError::Occupied);
+        }
+        let hash = state.hasher.hash_one(from);
+        let mut shard = state.shard(hash).write();
+        match shard.entry(hash, from, &state.hasher) {
+            Ok(symbol) if symbol == to => Ok(()),
+            Ok(_) => Err(InternError::Occupied),
+            Err(entry) => {
+                let mut table = state.table.lock();
+                if from.len() > table.length {
+                    return Err(InternError::TooLong);
+                }
+                if table.bytes + from.len() > table.limit.1 {
+                    return Err(InternError::CapacityExceeded);
+                }
+                table.bytes += from.len();
+                #[cfg(feature = "metrics")]
+                monitoring::resize(table.bytes);
+                entry.insert((table.arena.store(from), to));
+                Ok(())
+            }
+        }
+    }
+
+    /// Find an existing instance by its identifier.
+    ///
+    /// The identifier is validated against the current size of the storage.
+    #[inline]
+    pub fn from_id(id: usize) -> Option<Self> {
+        if id < Self::count() {
+            Self::from_index(id)
+        } else {
+            None
+        }
+    }
+
+    /// Find an existing instance by its identifier or fail with
+    /// `InternError::InvalidId`.
+    #[inline]
+    pub fn try_from_id(id: usize) -> Result<Self, InternError> {
+        Self::from_id(id).ok_or(InternError::InvalidId)
+    }
+
+    /// Refer to a string reserved via `Config::reserved` by its identifier in
+    /// constant contexts.
+    ///
+    /// The instance refers to whichever string has the identifier, which is
+    /// the intended one only if the storage is configured with the same
+    /// reserved strings before it is used.
+    ///
+    /// The function panics if the identifier does not fit into a symbol.
+    #[inline]
+    pub const fn reserved(id: usize) -> Self {
+        match Raw::new((id + 1) as _) {
+            Some(raw) if id < Raw::MAX.get() as usize => Self(raw),
+            _ => panic!("the identifier is out of range"),
+        }
+    }
+
+    /// Return the identifier in a fixed-width form for exchanging with a
+    /// process sharing the same table.
+    ///
+    /// The function returns `None` if the identifier does not fit in four
+    /// bytes.
+    #[inline]
+    pub fn to_wire(&self) -> Option<[u8; 4]> {
+        u32::try_from(self.id()).ok().map(u32::to_le_bytes)
+    }
+
+    /// Find an existing instance by its identifier in a fixed-width form.
+    ///
+    /// The identifier is validated against `count`, the size of the table
+    /// shared with the other process, which is to be confirmed via
+    /// `fingerprint`.
+    #[inline]
+    pub fn from_wire(bytes: [u8; 4], count: usize) -> Option<Self> {
+        let id = u32::from_le_bytes(bytes) as usize;
+        if id < count {
+            Self::from_id(id)
+        } else {
+            None
+        }
+    }
+
+    /// Compute a fingerprint of the strings with identifiers below `count`.
+    ///
+    /// The fingerprint does not depend on the platform, and comparing it to
+    /// the one of the other process detects mismatched tables. The function
+    /// returns `None` if fewer strings are stored.
+    pub fn fingerprint(count: usize) -> Option<u32> {
+        use core::hash::Hasher as _;
+
+        let values = &Box::instance().values;
+        if count > values.len() {
+            return None;
+        }
+        let mut hasher = SymbolHasher::default();
+        for index in 0..count {
+            let value = values.get(index)?;
+            hasher.write_u64(value.len() as u64);
+            hasher.write(value.as_bytes());
+        }
+        let hash = hasher.finish();
+        Some((hash >> 32) as u32 ^ hash as u32)
+    }
+
+    /// Find an existing instance without creating one.
+    #[inline]
+    pub fn get(value: &str) -> Option<Self> {
+        let state = Box::instance();
+        if let Some(value) = state.fold(value) {
+            return state.find(state.hasher.hash_one(&value), &value);
+        }
+        state.find(state.hasher.hash_one(value), value)
+    }
+
+    /// Iterate over all instances in the order of creation.
+    ///
+    /// The iterator covers the instances that exist at the time of the call.
+    #[inline]
+    pub fn all() -> impl Iterator<Item = Self> {
+        (0..Self::count()).filter_map(Self::from_index)
+    }
+
+    /// Iterate over all underlying strings in the order of creation.
+    #[inline]
+    pub fn all_strs() -> impl Iterator<Item = &'static str> {
+        let values = &Box::instance().values;
+        (0..values.len()).filter_map(|index| values.get(index))
+    }
+
+    /// Return the number of unique strings stored, including the empty one.
+    #[inline]
+    pub fn count() -> usize {
+        Box::instance().values.len()
+    }
+
+    /// Check if a string has already been stored.
+    #[inline]
+    pub fn is_interned(value: &str) -> bool {
+        Self::get(value).is_some()
+    }
+
+    /// Check if the instance has not been revoked via `rollback`.
+    pub fn is_valid(&self) -> bool {
+        let revoked = Box::instance().revoked.read();
+        let index = revoked.partition_point(|&(_, end)| end <= self.id());
+        revoked
+            .get(index)
+            .is_none_or(|&(start, _)| self.id() < start)
+    }
+
+    /// Compare with another instance by the order of creation.
+    ///
+    /// Unlike `Ord`, which is lexicographic, the comparison does not require
+    /// resolving the underlying strings.
+    #[inline]
+    pub fn cmp_by_id(&self, other: &Self) -> core::cmp::Ordering {
+        self.id().cmp(&other.id())
+    }
+
+    /// Return the identifier.
+    #[inline]
+    pub fn id(&self) -> usize {
+        (self.0.get() - 1) as usize
+    }
+
+    /// Return the length of the underlying string.
+    ///
+    /// The length is read from the table resolving identifiers, which does not
+    /// involve any locking, since the identifier leaves no room for it.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.as_static_str().len()
+    }
+
+    /// Check if the underlying string is empty without resolving it.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::EMPTY
+    }
+
+    /// Return the underlying string.
+    ///
+    /// Since strings are never deallocated, the reference is static. The
+    /// resolution does not involve any locking.
+    #[inline]
+    pub fn as_static_str(&self) -> &'static str {
+        if *self == Self::EMPTY {
+            return "";
+        }
+        Box::instance().values.get(self.id()).unwrap()
+    }
+
+    /// Return the underlying string as a C string.
+    ///
+    /// The function panics if the string contains a nul byte.
+    #[cfg(feature = "cstr")]
+    pub fn as_cstr(&self) -> &'static core::ffi::CStr {
+        let value = self.as_static_str();
+        if value.is_empty() {
+            return c"";
+        }
+        // SAFETY: Every nonempty string is stored with a trailing nul byte.
+        let bytes = unsafe { core::slice::from_raw_parts(value.as_ptr(), value.len() + 1) };
+        core::ffi::CStr::from_bytes_with_nul(bytes).expect("the string contains a nul byte")
+    }
+
+    /// Check if two instances are equal ignoring the ASCII case.
+    ///
+    /// The lowercase counterparts are stored and reused in subsequent calls.
+    pub fn eq_ignore_ascii_case(&self, other: &Self) -> bool {
+        if self == other {
+            return true;
+        }
+        match (self.ascii_lowercase(), other.ascii_lowercase()) {
+            (Some(one), Some(other)) => one == other,
+            _ => self.eq_str_ignore_ascii_case(other),
+        }
+    }
+
+    /// Check if the underlying string is equal to another one ignoring the
+    /// ASCII case.
+    #[inline]
+    pub fn eq_str_ignore_ascii_case(&self, other: &str) -> bool {
+        self.as_static_str().eq_ignore_ascii_case(other)
+    }
+
+    /// Return the instance of the full Unicode case folding.
+    ///
+    /// Two instances are equal ignoring the case if their foldings are equal.
+    /// The foldings are stored and reused in subsequent calls. The function
+    /// panics if the limit set via `limit` is reached or the storage is frozen
+    /// via `freeze`.
+    #[cfg(feature = "unicode-folding")]
+    #[cfg_attr(feature = "debug-tracking", track_caller)]
+    pub fn folded(&self) -> Self {
+        let folded = Box::instance().folded.read();
+        if let Some(&symbol) = folded.get(&self.id()) {
+            return symbol;
+        }
+        drop(folded);
+        let symbol = Self::new_folded(self.as_static_str());
+        let mut folded = Box::instance().folded.write();
+        folded.insert(self.id(), symbol);
+        symbol
+    }
+
+    /// Call a function with the underlying string borrowed for the duration of
+    /// the call.
+    ///
+    /// No lock is held while the function is called, so the function can
+    /// create new instances.
+    #[inline]
+    pub fn with<F, T>(&self, function: F) -> T
+    where
+        F: FnOnce(&str) -> T,
+    {
+        function(self.as_static_str())
+    }
+
+    /// Return the underlying strings of several instances at once.
+    #[inline]
+    pub fn resolve_many(symbols: &[Self]) -> Vec<&'static str> {
+        Self::resolve_iter(symbols.iter().copied())
+    }
+
+    /// Return the underlying strings of several instances at once.
+    #[inline]
+    pub fn resolve_iter<T>(symbols: T) -> Vec<&'static str>
+    where
+        T: IntoIterator<Item = Self>,
+    {
+        let symbols = symbols.into_iter();
+        symbols.map(|symbol| symbol.as_static_str()).collect()
+    }
+
+    fn ascii_lowercase(self) -> Option<Self> {
+        let lowercase = Box::instance().lowercase.read();
+        if let Some(&symbol) = lowercase.get(&self.id()) {
+            return Some(symbol);
+        }
+        drop(lowercase);
+        let value = self.as_static_str();
+        let symbol = if value.bytes().any(|byte| byte.is_ascii_uppercase()) {
+            Self::try_new_exact(value.to_ascii_lowercase()).ok()?
+        } else {
+            self
+        };
+        let mut lowercase = Box::instance().lowercase.write();
+        lowercase.insert(self.id(), symbol);
+        Some(symbol)
+    }
+
+    // Same as `try_new` but bypassing aliases, so that the instance has the
+    // string after the transformations set via `Config`.
+    #[cfg_attr(feature = "debug-tracking", track_caller)]
+    fn try_new_exact(value: String) -> Result<Self, InternError> {
+        let state = Box::instance();
+        let value = state.fold(&value).unwrap_or(value);
+        let symbol = Self::try_new(value.as_str())?;
+        if symbol.as_static_str() == value {
+            return Ok(symbol);
+        }
+        state.shadow(value)
+    }
+
+    #[inline]
+    fn from_index(index: usize) -> Option<Self> {
+        let value = index.checked_add(1)?.try_into().ok()?;
+        Raw::new(value).map(Self)
+    }
+}
+
+impl AsRef<[u8]> for Symbol {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.as_static_str().as_bytes()
+    }
+}
+
+#[cfg(feature = "std")]
+impl AsRef<std::ffi::OsStr> for Symbol {
+    #[inline]
+    fn as_ref(&self) -> &std::ffi::OsStr {
+        self.as_static_str().as_ref()
+    }
+}
+
+#[cfg(feature = "std")]
+impl AsRef<std::path::Path> for Symbol {
+    #[inline]
+    fn as_ref(&self) -> &std::path::Path {
+        self.as_static_str().as_ref()
+    }
+}
+
+impl AsRef<str> for Symbol {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_static_str()
+    }
+}
+
+impl Default for Symbol {
+    #[inline]
+    fn default() -> Self {
+        Self::EMPTY
+    }
+}
+
+impl From<&str> for Symbol {
+    #[inline]
+    fn from(value: &str) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<&mut str> for Symbol {
+    #[inline]
+    fn from(value: &mut str) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<&String> for Symbol {
+    #[inline]
+    fn from(value: &String) -> Self {
+        Self::new(value.as_str())
+    }
+}
+
+impl From<String> for Symbol {
+    #[inline]
+    fn from(value: String) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<alloc::borrow::Cow<'_, str>> for Symbol {
+    #[inline]
+    fn from(value: alloc::borrow::Cow<'_, str>) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<alloc::boxed::Box<str>> for Symbol {
+    #[inline]
+    fn from(value: alloc::boxed::Box<str>) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<Symbol> for String {
+    #[inline]
+    fn from(value: Symbol) -> Self {
+        value.as_static_str().into()
+    }
+}
+
+impl From<Symbol> for alloc::borrow::Cow<'static, str> {
+    #[inline]
+    fn from(value: Symbol) -> Self {
+        Self::Borrowed(value.as_static_str())
+    }
+}
+
+impl From<Symbol> for alloc::boxed::Box<str> {
+    #[inline]
+    fn from(value: Symbol) -> Self {
+        value.as_static_str().into()
+    }
+}
+
+impl From<Symbol> for alloc::sync::Arc<str> {
+    #[inline]
+    fn from(value: Symbol) -> Self {
+        value.as_static_str().into()
+    }
+}
+
+impl core::borrow::Borrow<str> for Symbol {
+    #[inline]
+    fn borrow(&self) -> &str {
+        self.as_static_str()
+    }
+}
+
+impl core::cmp::Eq for Symbol {}
+
+/// Lexicographic comparison of the underlying strings.
+///
+/// Ties, which only occur for instances revoked via `rollback`, are broken by
+/// the identifiers, so that the order is consistent with `Eq`.
+impl core::cmp::Ord for Symbol {
+    #[inline]
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        if self == other {
+            return core::cmp::Ordering::Equal;
+        }
+        let order = self.as_static_str().cmp(other.as_static_str());
+        order.then_with(|| self.cmp_by_id(other))
+    }
+}
+
+impl core::cmp::PartialEq for Symbol {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl core::cmp::PartialEq<str> for Symbol {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        self.as_static_str() == other
+    }
+}
+
+impl core::cmp::PartialEq<&str> for Symbol {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        *self == **other
+    }
+}
+
+impl core::cmp::PartialEq<String> for Symbol {
+    #[inline]
+    fn eq(&self, other: &String) -> bool {
+        *self == **other
+    }
+}
+
+impl core::cmp::PartialEq<Symbol> for str {
+    #[inline]
+    fn eq(&self, other: &Symbol) -> bool {
+        *other == *self
+    }
+}
+
+impl core::cmp::PartialEq<Symbol> for &str {
+    #[inline]
+    fn eq(&self, other: &Symbol) -> bool {
+        *other == **self
+    }
+}
+
+impl core::cmp::PartialEq<Symbol> for String {
+    #[inline]
+    fn eq(&self, other: &Symbol) -> bool {
+        *other == **self
+    }
+}
+
+impl core::cmp::PartialOrd for Symbol {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Formatting of the underlying string, which includes the identifier in the
+/// alternate form as in `Symbol(42: "foo")`.
+impl core::fmt::Debug for Symbol {
+    #[inline]
+    fn fmt(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if formatter.alternate() {
+            return write!(
+                formatter,
+                "Symbol({}: {:?})",
+                self.id(),
+                self.as_static_str()
+            );
+        }
+        core::fmt::Debug::fmt(self.as_static_str(), formatter)
+    }
+}
+
+/// Formatting of the underlying string, which includes the identifier in the
+/// alternate form as in `Symbol(42: "foo")`.
+impl core::fmt::Display for Symbol {
+    #[inline]
+    fn fmt(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if formatter.alternate() {
+            return core::fmt::Debug::fmt(self, formatter);
+        }
+        core::fmt::Display::fmt(self.as_static_str(), formatter)
+    }
+}
+
+/// Hashing of the underlying string, which is consistent with `Borrow<str>`.
+impl core::hash::Hash for Symbol {
+    #[inline]
+    fn hash<T: core::hash::Hasher>(&self, state: &mut T) {
+        self.as_static_str().hash(state)
+    }
+}
+
+impl core::ops::Deref for Symbol {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_static_str()
+    }
+}
+
+impl core::str::FromStr for Symbol {
+    type Err = core::convert::Infallible;
+
+    #[inline]
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Ok(Self::new(value))
+    }
+}
+
+impl core::fmt::Display for InternError {
+    fn fmt(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::CapacityExceeded => formatter.write_str("the capacity is exceeded"),
+            Self::Frozen => formatter.write_str("the storage is frozen"),
+            Self::TooLong => formatter.write_str("the string is too long"),
+            Self::InvalidId => formatter.write_str("the identifier is invalid"),
+            Self::Occupied => formatter.write_str("the string is already interned"),
+        }
+    }
+}
+
+impl core::error::Error for InternError {}
+
+impl Default for Config {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            capacity: 0,
+            max_symbols: usize::MAX,
+            max_bytes: usize::MAX,
+            max_length: usize::MAX,
+            case_folding: false,
+            #[cfg(feature = "normalization")]
+            normalization: false,
+            reserved: &[],
+        }
+    }
+}
+
+impl core::fmt::Display for ConfigureError {
+    fn fmt(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        formatter.write_str("the storage is already in use")
+    }
+}
+
+impl core::error::Error for ConfigureError {}
+
+impl core::fmt::Display for FromUtf16Error {
+    fn fmt(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Decode(error) => error.fmt(formatter),
+            Self::Intern(error) => error.fmt(formatter),
+        }
+    }
+}
+
+impl core::error::Error for FromUtf16Error {}
+
+impl From<InternError> for FromUtf16Error {
+    #[inline]
+    fn from(error: InternError) -> Self {
+        Self::Intern(error)
+    }
+}
+
+impl From<core::char::DecodeUtf16Error> for FromUtf16Error {
+    #[inline]
+    fn from(error: core::char::DecodeUtf16Error) -> Self {
+        Self::Decode(error)
+    }
+}
+
+impl Default for Box {
+    fn default() -> Self {
+        let state = Self {
+            shards: core::array::from_fn(|_| Default::default()),
+            table: Mutex::new(Table {
+                arena: Default::default(),
+                bytes: 0,
+                limit: (usize::MAX, usize::MAX),
+                length: usize::MAX,
+            }),
+            frozen: OnceLock::new(),
+            saved: AtomicUsize::new(0),
+            values: Values {
+                chunks: core::array::from_fn(|_| OnceLock::new()),
+                length: AtomicUsize::new(0),
+            },
+            lowercase: Default::default(),
+            #[cfg(feature = "unicode-folding")]
+            folded: Default::default(),
+            shadowed: Default::default(),
+            observers: Default::default(),
+            revoked: Default::default(),
+            config: Default::default(),
+            folding: false,
+            #[cfg(feature = "normalization")]
+            normalization: false,
+            hasher: Default::default(),
+        };
+        let hash = state.hasher.hash_one("");
+        let mut shard = state.shard(hash).write();
+        shard.map.insert_unique(hash, ("", Symbol::EMPTY), |_| hash);
+        drop(shard);
+        state.values.push("");
+        state
+    }
+}
+
+impl Box {
+    fn instance() -> &'static Self {
+        STATE.get_or_init(Default::default)
+    }
+
+    fn configure(config: Config) -> Self {
+        let mut state = Self::default();
+        let mut table = state.table.lock();
+        table.limit = (config.max_symbols, config.max_bytes);
+        table.length = config.max_length;
+        drop(table);
+        state.config = config;
+        state.folding = config.case_folding;
+        #[cfg(feature = "normalization")]
+        {
+            state.normalization = config.normalization;
+        }
+        state.reserve(config.capacity.max(config.reserved.len()), 0);
+        state.intern_reserved();
+        state
+    }
+
+    // Intern the strings reserved via `Config::reserved`.
+    fn intern_reserved(&self) {
+        for (index, &value) in self.config.reserved.iter().enumerate() {
+            let hash = self.hasher.hash_one(value);
+            let symbol = match self.intern(hash, value, Arena::keep) {
+                Ok(symbol) => symbol,
+                Err(error) => panic!("{error}"),
+            };
+            assert_eq!(
+                symbol.id(),
+                index + 1,
+                "the reserved strings should be distinct and nonempty"
+            );
+        }
+    }
+
+    // Check if strings are transformed before interning.
+    #[inline]
+    fn transforms(&self) -> bool {
+        #[cfg(feature = "normalization")]
+        if self.normalization {
+            return true;
+        }
+        self.folding
+    }
+
+    #[inline]
+    fn fold(&self, value: &str) -> Option<String> {
+        #[cfg(feature = "normalization")]
+        if self.normalization && !unicode_normalization::is_nfc(value) {
+            use unicode_normalization::UnicodeNormalization;
+
+            let mut value = value.nfc().collect::<String>();
+            if self.folding {
+                value.make_ascii_lowercase();
+            }
+            return Some(value);
+        }
+        if self.folding && value.bytes().any(|byte| byte.is_ascii_uppercase()) {
+            Some(value.to_ascii_lowercase())
+        } else {
+            None
+        }
+    }
+
+    fn reserve(&self, symbols: usize, bytes: usize) {
+        let mut table = self.table.lock();
+        table.arena.reserve(bytes);
+        self.values
+            .reserve(self.values.len().saturating_add(symbols));
+        drop(table);
+        for shard in &self.shards {
+            let mut shard = shard.write();
+            let hasher = |&(key, _): &(&str, Symbol)| self.hasher.hash_one(key);
+            shard.migrate(&self.hasher, usize::MAX);
+            shard.map.reserve(symbols.div_ceil(SHARDS), hasher);
+        }
+    }
+
+    #[cfg_attr(feature = "debug-tracking", track_caller)]
+    fn intern<T, F>(&self, hash: u64, value: T, store: F) -> Result<Symbol, InternError>
+    where
+        T: AsRef<str>,
+        F: FnOnce(&mut Arena, T) -> &'static str,
+    {
+        let length = value.as_ref().len();
+        if let Some(frozen) = self.frozen.get() {
+            let symbol = frozen
+                .find(hash, value.as_ref())
+                .ok_or(InternError::Frozen)?;
+            return Ok(self.hit(symbol, length));
+        }
+        let shard = self.shard(hash);
+        if let Some(symbol) = shard.read().find(hash, value.as_ref()) {
+            return Ok(self.hit(symbol, length));
+        }
+        let mut shard = shard.write();
+        let symbol = match shard.entry(hash, value.as_ref(), &self.hasher) {
+            Ok(symbol) => return Ok(self.hit(symbol, length)),
+            Err(entry) => self.occupy(entry, &mut self.table.lock(), value, store)?,
+        };
+        drop(shard);
+        self.notify(symbol.id()..symbol.id() + 1);
+        Ok(symbol)
+    }
+
+    // Same as `intern` but giving up instead of waiting for locks.
+    #[cfg(feature = "async")]
+    fn try_intern(&self, hash: u64, value: &str) -> Option<Result<Symbol, InternError>> {
+        let length = value.len();
+        if let Some(frozen) = self.frozen.get() {
+            let symbol = frozen.find(hash, value).ok_or(InternError::Frozen);
+            return Some(symbol.map(|symbol| self.hit(symbol, length)));
+        }
+        let shard = self.shard(hash);
+        if let Some(symbol) = shard.try_read()?.find(hash, value) {
+            return Some(Ok(self.hit(symbol, length)));
+        }
+        let mut shard = shard.try_write()?;
+        let symbol = match shard.entry(hash, value, &self.hasher) {
+            Ok(symbol) => return Some(Ok(self.hit(symbol, length))),
+            Err(entry) => {
+                let mut table = self.table.try_lock()?;
+                self.occupy(entry, &mut table, value, Arena::store)
+            }
+        };
+        drop(shard);
+        if let Ok(symbol) = symbol {
+            self.notify(symbol.id()..symbol.id() + 1);
+        }
+        Some(symbol)
+    }
+
+    #[inline]
+    fn find(&self, hash: u64, value: &str) -> Option<Symbol> {
+        if let Some(frozen) = self.frozen.get() {
+            return frozen.find(hash, value);
+        }
+        self.shard(hash).read().find(hash, value)
+    }
+
+    #[inline]
+    fn shard(&self, hash: u64) -> &RwLock<Shard> {
+        &self.shards[Self::locate(hash)]
+    }
+
+    // Call the observers for a range of new identifiers, which is done without
+    // holding any locks, so that the observers can intern strings themselves.
+    fn notify(&self, ids: core::ops::Range<usize>) {
+        if ids.is_empty() {
+            return;
+        }
+        let observers = self.observers.read().clone();
+        for id in ids {
+            let value = self.values.get(id).unwrap();
+            for observer in observers.iter() {
+                observer(value, id);
+            }
+        }
+    }
+
+    #[inline]
+    fn hit(&self, symbol: Symbol, length: usize) -> Symbol {
+        self.saved.fetch_add(length, Ordering::Relaxed);
+        #[cfg(feature = "metrics")]
+        monitoring::hit();
+        symbol
+    }
 
-use std::collections::BTreeSet;
-use std::sync::{OnceLock, RwLock};
+    #[inline]
+    fn locate(hash: u64) -> usize {
+        // The bits used by the tables within shards are avoided.
+        (hash >> 32) as usize % SHARDS
+    }
 
-#[derive(Default)]
-struct Box(BTreeSet<&'static str>);
+    #[cfg_attr(feature = "debug-tracking", track_caller)]
+    fn occupy<T, F>(
+        &self,
+        entry: VacantEntry<'_, (&'static str, Symbol)>,
+        table: &mut Table,
+        value: T,
+        store: F,
+    ) -> Result<Symbol, InternError>
+    where
+        T: AsRef<str>,
+        F: FnOnce(&mut Arena, T) -> &'static str,
+    {
+        let (value, symbol) = self.allocate(table, value, store)?;
+        entry.insert((value, symbol));
+        Ok(symbol)
+    }
 
-/// A unique static string.
-#[derive(Clone, Copy, Ord, PartialOrd)]
-pub struct Symbol(&'static str);
+    // Store a string shadowed by an alias, which is not found via the shards,
+    // so that the instance has the string itself.
+    #[cfg_attr(feature = "debug-tracking", track_caller)]
+    fn shadow(&self, value: String) -> Result<Symbol, InternError> {
+        let mut shadowed = self.shadowed.write();
+        if let Some(&symbol) = shadowed.get(value.as_str()) {
+            return Ok(symbol);
+        }
+        let (value, symbol) = self.allocate(&mut self.table.lock(), value, Arena::store)?;
+        shadowed.insert(value, symbol);
+        drop(shadowed);
+        self.notify(symbol.id()..symbol.id() + 1);
+        Ok(symbol)
+    }
 
-impl Symbol {
-    /// Create a new instance.
-    pub fn new<T>(value: T) -> Self
+    #[cfg_attr(feature = "debug-tracking", track_caller)]
+    fn allocate<T, F>(
+        &self,
+        table: &mut Table,
+        value: T,
+        store: F,
+    ) -> Result<(&'static str, Symbol), InternError>
     where
-        T: AsRef<str> + Into<String>,
+        T: AsRef<str>,
+        F: FnOnce(&mut Arena, T) -> &'static str,
     {
-        let mut state = Box::instance().write().unwrap();
-        if let Some(value) = state.0.get(value.as_ref()) {
-            return Self(value);
+        if self.frozen.get().is_some() {
+            return Err(InternError::Frozen);
+        }
+        let (symbols, bytes) = table.limit;
+        let index = self.values.len();
+        let length = value.as_ref().len();
+        if length > table.length {
+            return Err(InternError::TooLong);
         }
-        let value = value.into().leak();
-        state.0.insert(value);
-        Self(value)
+        if index >= symbols || table.bytes + length > bytes {
+            return Err(InternError::CapacityExceeded);
+        }
+        let symbol = Symbol::from_index(index).ok_or(InternError::CapacityExceeded)?;
+        let value = store(&mut table.arena, value);
+        self.values.push(value);
+        #[cfg(feature = "debug-tracking")]
+        tracking::record(core::panic::Location::caller());
+        table.bytes += length;
+        #[cfg(feature = "metrics")]
+        monitoring::miss(table.bytes);
+        Ok((value, symbol))
     }
 }
 
-impl AsRef<str> for Symbol {
+impl Arena {
+    fn store<T>(&mut self, value: T) -> &'static str
+    where
+        T: AsRef<str> + Into<String>,
+    {
+        let length = value.as_ref().len();
+        let size = length + TERMINATOR.len();
+        if length > ARENA / 4 {
+            let mut value = value.into();
+            value.push_str(TERMINATOR);
+            let value = alloc::boxed::Box::leak(value.into_boxed_str());
+            return &value[..length];
+        }
+        if self.chunk.len() < size {
+            self.allocate(ARENA);
+        }
+        let (head, tail) = core::mem::take(&mut self.chunk).split_at_mut(size);
+        let (head, terminator) = head.split_at_mut(length);
+        head.copy_from_slice(value.as_ref().as_bytes());
+        terminator.fill(0);
+        self.chunk = tail;
+        self.used += size;
+        // SAFETY: The bytes have been copied from a string.
+        unsafe { core::str::from_utf8_unchecked(head) }
+    }
+
+    fn store_bytes(&mut self, value: &[u8]) -> &'static [u8] {
+        let length = value.len();
+        if length > ARENA / 4 {
+            return alloc::boxed::Box::leak(value.into());
+        }
+        if self.chunk.len() < length {
+            self.allocate(ARENA);
+        }
+        let (head, tail) = core::mem::take(&mut self.chunk).split_at_mut(length);
+        head.copy_from_slice(value);
+        self.chunk = tail;
+        self.used += length;
+        head
+    }
+
+    // Keep a static string as is unless strings have to be terminated.
     #[inline]
-    fn as_ref(&self) -> &str {
-        self.0
+    fn keep(&mut self, value: &'static str) -> &'static str {
+        if TERMINATOR.is_empty() {
+            value
+        } else {
+            self.store(value)
+        }
+    }
+
+    fn reserve(&mut self, bytes: usize) {
+        if self.chunk.len() < bytes {
+            self.allocate(bytes);
+        }
+    }
+
+    fn allocate(&mut self, bytes: usize) {
+        self.chunk = alloc::vec![0; bytes].leak();
+        self.allocated += bytes;
     }
 }
 
-impl Default for Symbol {
+impl Shard {
     #[inline]
-    fn default() -> Self {
-        Self::new::<&str>(Default::default())
+    fn find(&self, hash: u64, value: &str) -> Option<Symbol> {
+        let entry = self.map.find(hash, |&(key, _)| key == value);
+        let entry = entry.or_else(|| self.old.find(hash, |&(key, _)| key == value));
+        entry.map(|&(_, symbol)| symbol)
+    }
+
+    // Find an existing symbol or a vacant entry for a new one.
+    fn entry(
+        &mut self,
+        hash: u64,
+        value: &str,
+        hasher: &Hasher,
+    ) -> Result<Symbol, VacantEntry<'_, (&'static str, Symbol)>> {
+        self.migrate(hasher, MIGRATION);
+        if self.old.is_empty() && self.map.len() == self.map.capacity() {
+            let capacity = (2 * self.map.capacity()).max(4 * MIGRATION);
+            self.old = core::mem::replace(&mut self.map, HashTable::with_capacity(capacity));
+            self.cursor = 0;
+        }
+        if let Some(&(_, symbol)) = self.old.find(hash, |&(key, _)| key == value) {
+            return Ok(symbol);
+        }
+        let entry = self.map.entry(
+            hash,
+            |&(key, _)| key == value,
+            |&(key, _)| hasher.hash_one(key),
+        );
+        match entry {
+            Entry::Occupied(entry) => Ok(entry.get().1),
+            Entry::Vacant(entry) => Err(entry),
+        }
+    }
+
+    fn iter(&mut self, hasher: &Hasher) -> impl Iterator<Item = &(&'static str, Symbol)> {
+        self.migrate(hasher, usize::MAX);
+        self.map.iter()
+    }
+
+    fn migrate(&mut self, hasher: &Hasher, buckets: usize) {
+        if self.old.is_empty() {
+            return;
+        }
+        let end = self
+            .cursor
+            .saturating_add(buckets)
+            .min(self.old.num_buckets());
+        for cursor in self.cursor..end {
+            if let Some(&entry) = self.old.get_bucket(cursor) {
+                let hasher = |&(key, _): &(&str, Symbol)| hasher.hash_one(key);
+                self.map.insert_unique(hasher(&entry), entry, hasher);
+            }
+        }
+        self.cursor = end;
+        if self.cursor == self.old.num_buckets() {
+            self.old = HashTable::new();
+            self.cursor = 0;
+        }
     }
 }
 
-impl<T> From<T> for Symbol
-where
-    T: AsRef<str> + Into<String>,
-{
+impl Values {
     #[inline]
-    fn from(value: T) -> Self {
-        Self::new(value)
+    fn get(&self, index: usize) -> Option<&'static str> {
+        if index >= self.len() {
+            return None;
+        }
+        let (chunk, offset) = Self::locate(index)?;
+        let slot = self.chunks.get(chunk)?.get()?.get(offset)?;
+        let pointer = slot.pointer.load(Ordering::Relaxed);
+        let length = slot.length.load(Ordering::Relaxed);
+        // SAFETY: The slot has been set to a static string before the length
+        // was published, and it is never changed afterwards.
+        unsafe {
+            let value = core::slice::from_raw_parts(pointer, length);
+            Some(core::str::from_utf8_unchecked(value))
+        }
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.length.load(Ordering::Acquire)
+    }
+
+    fn capacity(&self) -> usize {
+        let chunks = self.chunks.iter().filter_map(OnceLock::get);
+        chunks.map(|chunk| chunk.len()).sum()
+    }
+
+    fn reserve(&self, length: usize) {
+        let Some((last, _)) = Self::locate(length.saturating_sub(1)) else {
+            return;
+        };
+        for chunk in 0..=last {
+            self.chunks[chunk].get_or_init(|| Self::allocate(chunk));
+        }
+    }
+
+    // The function must be called with the table locked.
+    fn push(&self, value: &'static str) {
+        let index = self.length.load(Ordering::Relaxed);
+        let (chunk, offset) = Self::locate(index).unwrap();
+        let chunk = self.chunks[chunk].get_or_init(|| Self::allocate(chunk));
+        let slot = &chunk[offset];
+        slot.pointer
+            .store(value.as_ptr().cast_mut(), Ordering::Relaxed);
+        slot.length.store(value.len(), Ordering::Relaxed);
+        self.length.store(index + 1, Ordering::Release);
     }
-}
 
-impl std::cmp::Eq for Symbol {}
+    fn allocate(chunk: usize) -> alloc::boxed::Box<[Slot]> {
+        let slots = alloc::boxed::Box::new_zeroed_slice(1 << (chunk + CHUNK_BITS));
+        // SAFETY: Zeroed atomics are valid.
+        unsafe { slots.assume_init() }
+    }
 
-impl std::cmp::PartialEq for Symbol {
     #[inline]
-    fn eq(&self, other: &Self) -> bool {
-        self.as_ptr() == other.as_ptr()
+    fn locate(index: usize) -> Option<(usize, usize)> {
+        let index = index.checked_add(1 << CHUNK_BITS)?;
+        let chunk = (usize::BITS - 1 - index.leading_zeros()) as usize - CHUNK_BITS;
+        Some((chunk, index - (1 << (chunk + CHUNK_BITS))))
     }
 }
 
-impl std::fmt::Debug for Symbol {
-    #[inline]
-    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        std::fmt::Debug::fmt(self.0, formatter)
+/// Freeze the storage.
+///
+/// Once frozen, the storage serves lookups from an immutable snapshot without
+/// locking, and strings that have not been stored before are rejected:
+/// `Symbol::try_new` fails, and `Symbol::new` panics.
+pub fn freeze() {
+    let state = Box::instance();
+    let mut shards = state.shards.each_ref().map(RwLock::write);
+    let mut frozen = Shard::default();
+    let entries = shards
+        .iter_mut()
+        .flat_map(|shard| shard.iter(&state.hasher));
+    for &entry in entries {
+        let hasher = |&(key, _): &(&str, Symbol)| state.hasher.hash_one(key);
+        frozen.map.insert_unique(hasher(&entry), entry, hasher);
     }
+    let _ = state.frozen.set(frozen);
 }
 
-impl std::fmt::Display for Symbol {
-    #[inline]
-    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        std::fmt::Display::fmt(self.0, formatter)
+/// Configure the storage before its first use.
+///
+/// The function fails if the storage has already been used or configured.
+pub fn configure(config: Config) -> Result<(), ConfigureError> {
+    let mut configured = false;
+    STATE.get_or_init(|| {
+        configured = true;
+        Box::configure(config)
+    });
+    if configured {
+        Ok(())
+    } else {
+        Err(ConfigureError)
     }
 }
 
-impl std::hash::Hash for Symbol {
-    #[inline]
-    fn hash<T: std::hash::Hasher>(&self, state: &mut T) {
-        self.as_ptr().hash(state)
+/// Register a function to be called whenever a new symbol is created.
+///
+/// The function receives the string and the identifier of the symbol. It is
+/// called after the symbol is created, without holding any locks, and should
+/// not register other functions.
+pub fn observe<F>(function: F)
+where
+    F: Fn(&'static str, usize) + Send + Sync + 'static,
+{
+    let state = Box::instance();
+    let mut observers = state.observers.write();
+    let mut functions = observers.to_vec();
+    functions.push(alloc::sync::Arc::new(function));
+    *observers = functions.into();
+}
+
+/// Record the current state of the storage.
+#[inline]
+pub fn checkpoint() -> Checkpoint {
+    Checkpoint(Box::instance().values.len())
+}
+
+/// Revoke the instances created after a checkpoint.
+///
+/// The revoked instances are no longer found by their strings, and creating
+/// an instance for the same string yields a new identifier, so that revoked
+/// instances are never equal to new ones, which can be detected via
+/// `Symbol::is_valid`. Identifiers are not reused, and the strings are kept,
+/// since they are handed out as static. The function panics if the storage is
+/// frozen via `freeze`.
+///
+/// A revoked instance and the new one for the same string are not equal but
+/// have the same hash and borrow the same string, so maps keyed by instances
+/// and looked up via `Borrow<str>` must not contain both.
+pub fn rollback(checkpoint: Checkpoint) {
+    let state = Box::instance();
+    let mut shards = state.shards.each_ref().map(RwLock::write);
+    let mut shadowed = state.shadowed.write();
+    let mut table = state.table.lock();
+    if state.frozen.get().is_some() {
+        panic!("{}", InternError::Frozen);
+    }
+    let (mut start, end) = (checkpoint.0, state.values.len());
+    if start >= end {
+        return;
+    }
+    for shard in &mut shards {
+        shard.migrate(&state.hasher, usize::MAX);
+        shard.map.retain(|&mut (value, symbol)| {
+            let keep = symbol.id() < checkpoint.0;
+            if !keep {
+                table.bytes -= value.len();
+            }
+            keep
+        });
+    }
+    shadowed.retain(|&value, symbol| {
+        let keep = symbol.id() < checkpoint.0;
+        if !keep {
+            table.bytes -= value.len();
+        }
+        keep
+    });
+    drop(shadowed);
+    let mut revoked = state.revoked.write();
+    while let Some(&(other, _)) = revoked.last().filter(|&&(_, other)| other >= start) {
+        start = start.min(other);
+        revoked.pop();
+    }
+    revoked.push((start, end));
+    drop(revoked);
+    #[cfg(feature = "metrics")]
+    monitoring::resize(table.bytes);
+    let mut lowercase = state.lowercase.write();
+    lowercase.retain(|&id, symbol| id < checkpoint.0 && symbol.id() < checkpoint.0);
+    #[cfg(feature = "unicode-folding")]
+    {
+        let mut folded = state.folded.write();
+        folded.retain(|&id, symbol| id < checkpoint.0 && symbol.id() < checkpoint.0);
     }
+    cache::invalidate();
 }
 
-impl std::ops::Deref for Symbol {
-    type Target = str;
+/// Clear the storage, so that identifiers start from scratch.
+///
+/// The function is meant for tests that make assertions about identifiers.
+/// Instances created before the call must not be used afterwards, since their
+/// identifiers are reused. The limits and the reserved strings given to
+/// `configure` are restored, which drops those set via `limit` afterwards. The
+/// strings are kept, and freezing via `freeze` is not undone.
+#[cfg(feature = "testing")]
+pub fn reset() {
+    let state = Box::instance();
+    let mut shards = state.shards.each_ref().map(RwLock::write);
+    let mut shadowed = state.shadowed.write();
+    let mut table = state.table.lock();
+    for shard in &mut shards {
+        shard.migrate(&state.hasher, usize::MAX);
+        shard.map.retain(|&mut (_, symbol)| symbol == Symbol::EMPTY);
+    }
+    shadowed.clear();
+    table.bytes = 0;
+    table.limit = (state.config.max_symbols, state.config.max_bytes);
+    table.length = state.config.max_length;
+    state.values.length.store(1, Ordering::Release);
+    state.saved.store(0, Ordering::Relaxed);
+    state.revoked.write().clear();
+    state.lowercase.write().clear();
+    #[cfg(feature = "unicode-folding")]
+    state.folded.write().clear();
+    cache::invalidate();
+    drop((shards, shadowed, table));
+    state.intern_reserved();
+}
 
-    #[inline]
-    fn deref(&self) -> &Self::Target {
-        self.0
+/// Release the unused capacity of the internal containers.
+///
+/// Strings are not moved, and the table resolving identifiers keeps its
+/// capacity, since it is read without locking.
+pub fn shrink_to_fit() {
+    let state = Box::instance();
+    for shard in &state.shards {
+        let mut shard = shard.write();
+        let hasher = |&(key, _): &(&str, Symbol)| state.hasher.hash_one(key);
+        shard.migrate(&state.hasher, usize::MAX);
+        shard.map.shrink_to_fit(hasher);
     }
+    state.lowercase.write().shrink_to_fit();
+    #[cfg(feature = "unicode-folding")]
+    state.folded.write().shrink_to_fit();
+    state.shadowed.write().shrink_to_fit();
 }
 
-impl Box {
-    fn instance() -> &'static RwLock<Self> {
-        static STATE: OnceLock<RwLock<Box>> = OnceLock::new();
-        STATE.get_or_init(|| RwLock::new(Default::default()))
+/// Return statistics about the storage.
+pub fn stats() -> Stats {
+    let state = Box::instance();
+    let mut overhead = 0;
+    for shard in &state.shards {
+        let shard = shard.read();
+        let capacity = shard.map.capacity() + shard.old.capacity();
+        overhead += capacity * (core::mem::size_of::<(&str, Symbol)>() + 1);
+    }
+    let table = state.table.lock();
+    overhead += table.arena.allocated - table.arena.used;
+    overhead += state.values.capacity() * core::mem::size_of::<Slot>();
+    Stats {
+        symbols: state.values.len(),
+        bytes: table.bytes,
+        overhead,
+        saved: state.saved.load(Ordering::Relaxed),
+    }
+}
+
+/// Limit the number of symbols, including the empty one, and the total number
+/// of bytes stored.
+///
+/// Once the limit is reached, `Symbol::try_new` fails and `Symbol::new` panics
+/// for strings that have not been stored before.
+pub fn limit(symbols: usize, bytes: usize) {
+    Box::instance().table.lock().limit = (symbols, bytes);
+}
+
+/// Reserve capacity for the given number of symbols and bytes in addition to
+/// those already stored.
+///
+/// Reserving ahead of bulk loading avoids repeated growth of the internal
+/// containers while holding locks.
+pub fn reserve(symbols: usize, bytes: usize) {
+    Box::instance().reserve(symbols, bytes);
+}
+
+// Split symbols into distinct strings and references into them.
+#[cfg(any(feature = "borsh", feature = "serde"))]
+fn tabulate<'l, T>(symbols: T) -> (Vec<&'static str>, Vec<usize>)
+where
+    T: IntoIterator<Item = &'l Symbol>,
+{
+    let mut ids = HashMap::<Symbol, usize, SymbolBuildHasher>::default();
+    let mut strings = Vec::new();
+    let mut references = Vec::new();
+    for &symbol in symbols {
+        let id = *ids.entry(symbol).or_insert_with(|| {
+            strings.push(symbol.as_static_str());
+            strings.len() - 1
+        });
+        references.push(id);
     }
+    (strings, references)
 }
 
+pub mod front;
+
+#[cfg(feature = "borsh")]
+pub mod borsh;
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
+#[cfg(feature = "lasso")]
+pub mod lasso;
+
+#[cfg(feature = "proptest")]
+pub mod proptest;
+
 #[cfg(feature = "serde")]
-mod serialization {
-    struct Visitor;
-
-    impl<'l> serde::de::Deserialize<'l> for super::Symbol {
-        #[inline]
-        fn deserialize<T>(deserializer: T) -> Result<Self, T::Error>
-        where
-            T: serde::de::Deserializer<'l>,
-        {
-            deserializer.deserialize_str(Visitor)
-        }
+pub mod serde;
+
+#[cfg(feature = "tracing")]
+pub mod tracing;
+
+mod backend;
+mod bounded;
+mod bytes;
+mod cache;
+mod hash;
+mod interner;
+mod lazy;
+mod pool;
+mod rc;
+mod scoped;
+mod sync;
+mod tagged;
+
+#[cfg(feature = "std")]
+mod dump;
+
+#[cfg(feature = "std")]
+mod path;
+
+#[cfg(feature = "arbitrary")]
+mod fuzzing;
+
+#[cfg(feature = "async")]
+mod asynchronous;
+
+#[cfg(feature = "debug-tracking")]
+mod tracking;
+
+#[cfg(feature = "defmt")]
+mod embedded;
+
+#[cfg(feature = "icu")]
+mod collation;
+
+#[cfg(feature = "local")]
+mod local;
+
+#[cfg(feature = "log-kv")]
+mod logging;
+
+#[cfg(feature = "metrics")]
+mod monitoring;
+
+#[cfg(feature = "pyo3")]
+mod python;
+
+#[cfg(feature = "quickcheck")]
+mod checking;
+
+#[cfg(feature = "rayon")]
+mod parallel;
+
+#[cfg(feature = "rkyv")]
+mod archive;
+
+#[cfg(feature = "schemars")]
+mod schema;
+
+#[cfg(any(feature = "diesel", feature = "sqlx"))]
+mod database;
+
+#[cfg(feature = "string_cache")]
+mod atom;
+
+/// Create a symbol.
+///
+/// String literals are stored without copying via `Symbol::new_static`, and
+/// other expressions are passed to `Symbol::new`.
+///
+/// ```
+/// use r#box::symbol;
+///
+/// assert_eq!(symbol!("foo"), symbol!(String::from("foo")));
+/// ```
+#[macro_export]
+macro_rules! symbol {
+    ($value:literal) => {
+        $crate::Symbol::new_static($value)
+    };
+    ($value:expr) => {
+        $crate::Symbol::new($value)
+    };
+}
+
+/// Define symbols created on first use.
+///
+/// Each item becomes a static `LazySymbol`, which dereferences to `Symbol`.
+///
+/// ```
+/// mod sym {
+///     r#box::symbols! {
+///         pub FOO = "foo",
+///         pub BAR = "bar",
+///     }
+/// }
+///
+/// assert_eq!(*sym::FOO, r#box::Symbol::new("foo"));
+/// assert_eq!(sym::BAR.as_static_str(), "bar");
+/// ```
+#[macro_export]
+macro_rules! symbols {
+    ($($(#[$attribute:meta])* $visibility:vis $name:ident = $value:expr),* $(,)?) => {
+        $(
+            $(#[$attribute])*
+            $visibility static $name: $crate::LazySymbol = $crate::LazySymbol::new($value);
+        )*
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FromUtf16Error, InternError, Interner, Symbol};
+
+    #[test]
+    fn absorb() {
+        let mut interner = Interner::new();
+        let one = interner.intern("absorb-foo");
+        let other = interner.intern("absorb-bar");
+        let table = Symbol::absorb(&interner);
+        assert_eq!(Symbol::EMPTY.remap(&table).unwrap(), "absorb-foo");
+        assert_eq!(
+            Symbol::from_id(other).unwrap().remap(&table).unwrap(),
+            "absorb-bar"
+        );
+        assert_eq!(
+            table.get(one),
+            Symbol::get("absorb-foo").map(|symbol| symbol.id())
+        );
     }
 
-    impl serde::ser::Serialize for super::Symbol {
-        #[inline]
-        fn serialize<T>(&self, serializer: T) -> Result<T::Ok, T::Error>
-        where
-            T: serde::ser::Serializer,
+    #[test]
+    fn alias() {
+        let color = Symbol::new("alias-color");
+        assert_eq!(Symbol::alias("alias-colour", color), Ok(()));
+        assert_eq!(Symbol::alias("alias-colour", color), Ok(()));
+        assert_eq!(Symbol::alias("alias-color", color), Ok(()));
+        assert_eq!(
+            Symbol::alias("alias-color", Symbol::EMPTY),
+            Err(InternError::Occupied),
+        );
+        assert_eq!(Symbol::alias("", color), Err(InternError::Occupied));
+        assert_eq!(Symbol::alias("", Symbol::EMPTY), Ok(()));
+        let invalid = Symbol::from_index(u32::MAX as usize - 1).unwrap();
+        assert_eq!(
+            Symbol::alias("alias-colors", invalid),
+            Err(InternError::InvalidId),
+        );
+        assert_eq!(Symbol::new("alias-colour"), color);
+        assert_ne!(color, "alias-colour");
+        assert_eq!(Symbol::new("alias-colour"), color);
+        assert_eq!(Symbol::get("alias-colour"), Some(color));
+        assert_eq!(Symbol::new("alias-colour").as_static_str(), "alias-color");
+
+        let colour = Symbol::new("Alias-Colour");
+        assert!(colour.eq_ignore_ascii_case(&Symbol::new("ALIAS-COLOUR")));
+        assert!(!colour.eq_ignore_ascii_case(&Symbol::new("ALIAS-COLOR")));
+        assert!(!colour.eq_str_ignore_ascii_case("ALIAS-COLOR"));
+        #[cfg(feature = "unicode-folding")]
         {
-            serializer.serialize_str(self.0)
+            assert_ne!(colour.folded(), color);
+            assert_eq!(colour.folded(), Symbol::new("ALIAS-COLOUR").folded());
+            assert_eq!(colour.folded().as_static_str(), "alias-colour");
+            assert_eq!(Symbol::new_folded("ALIAS-COLOUR"), colour.folded());
         }
     }
 
-    #[cfg(feature = "serde")]
-    impl<'l> serde::de::Visitor<'l> for Visitor {
-        type Value = super::Symbol;
+    #[test]
+    fn all() {
+        let one = Symbol::new("all");
+        assert_eq!(Symbol::all().next(), Some(Symbol::EMPTY));
+        assert!(Symbol::all().any(|symbol| symbol == one));
+        assert!(Symbol::all_strs().any(|value| value == "all"));
+    }
 
-        #[inline]
-        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-            formatter.write_str("a string")
+    #[test]
+    fn cache() {
+        let one = Symbol::new(String::from("cache"));
+        for _ in 0..10 {
+            assert_eq!(Symbol::new("cache"), one);
         }
+        super::set_thread_cache_capacity(1);
+        assert_eq!(Symbol::new("cache"), one);
+        assert_ne!(Symbol::new("cache-other"), one);
+        assert_eq!(Symbol::new("cache"), one);
+        super::set_thread_cache_capacity(64);
+    }
 
-        #[inline]
-        fn visit_str<T>(self, value: &str) -> Result<Self::Value, T>
-        where
-            T: serde::de::Error,
-        {
-            Ok(super::Symbol::new(value))
+    #[test]
+    fn case() {
+        let one = Symbol::new("Content-Type");
+        let other = Symbol::new("content-TYPE");
+        assert!(one.eq_ignore_ascii_case(&other));
+        assert!(other.eq_ignore_ascii_case(&one));
+        assert!(!one.eq_ignore_ascii_case(&Symbol::new("content-length")));
+        assert!(one.eq_str_ignore_ascii_case("CONTENT-type"));
+    }
+
+    #[test]
+    fn concurrent() {
+        let handles = (0..4).map(|_| {
+            std::thread::spawn(|| {
+                let values = (0..100).map(|index| format!("concurrent-{index}"));
+                values.map(Symbol::new).collect::<Vec<_>>()
+            })
+        });
+        let symbols = handles.map(|handle| handle.join().unwrap());
+        let symbols = symbols.collect::<Vec<_>>();
+        assert!(symbols.windows(2).all(|pair| pair[0] == pair[1]));
+        assert_eq!(symbols[0][42], "concurrent-42");
+    }
+
+    #[test]
+    fn convert() {
+        use std::borrow::Cow;
+        use std::sync::Arc;
+
+        let symbol = Symbol::from(Cow::from("convert"));
+        assert_eq!(Symbol::from(std::boxed::Box::from("convert")), symbol);
+        assert_eq!(String::from(symbol), "convert");
+        assert_eq!(std::boxed::Box::<str>::from(symbol), "convert".into());
+        assert_eq!(Arc::<str>::from(symbol), Arc::from("convert"));
+        assert!(matches!(Cow::from(symbol), Cow::Borrowed("convert")));
+
+        let mut value = String::from("convert");
+        assert_eq!(Symbol::from(value.as_mut_str()), symbol);
+        assert_eq!(Symbol::from(&value), symbol);
+        let other: Symbol = value.into();
+        assert_eq!(other, symbol);
+    }
+
+    #[test]
+    fn convert_reference() {
+        use std::ffi::OsStr;
+        use std::path::Path;
+
+        let symbol = Symbol::new("convert/reference");
+        assert_eq!(AsRef::<[u8]>::as_ref(&symbol), b"convert/reference");
+        assert_eq!(AsRef::<OsStr>::as_ref(&symbol), "convert/reference");
+        assert_eq!(
+            Path::new("root").join(symbol),
+            Path::new("root/convert/reference")
+        );
+    }
+
+    #[test]
+    fn count() {
+        assert!(!Symbol::is_interned("count"));
+        let count = Symbol::count();
+        Symbol::new("count");
+        assert!(Symbol::is_interned("count"));
+        assert!(Symbol::count() > count);
+    }
+
+    #[cfg(feature = "cstr")]
+    #[test]
+    fn cstr() {
+        assert_eq!(Symbol::new("cstr-foo").as_cstr(), c"cstr-foo");
+        assert_eq!(Symbol::new_static("cstr-bar").as_cstr(), c"cstr-bar");
+        assert_eq!(Symbol::EMPTY.as_cstr(), c"");
+        let value = "cstr".repeat(super::ARENA);
+        let one = Symbol::new(value.as_str());
+        assert_eq!(one.as_cstr().to_bytes(), value.as_bytes());
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(Symbol::new(""), Symbol::EMPTY);
+        assert_eq!(Symbol::default(), Symbol::EMPTY);
+        assert_eq!(Symbol::get(""), Some(Symbol::EMPTY));
+        assert_eq!(Symbol::EMPTY.id(), 0);
+        assert_eq!(&*Symbol::EMPTY, "");
+    }
+
+    #[test]
+    fn equal() {
+        use std::collections::HashMap;
+
+        assert_eq!(Symbol::new("foo"), Symbol::new("foo"));
+        assert_eq!(Symbol::new("foo"), Symbol::new(String::from("foo")));
+        assert_ne!(Symbol::new("foo"), Symbol::new("bar"));
+
+        assert_eq!(Symbol::new("foo"), "foo");
+        assert_eq!(Symbol::new("foo"), *"foo");
+        assert_eq!(Symbol::new("foo"), String::from("foo"));
+        assert_eq!("foo", Symbol::new("foo"));
+        assert_eq!(String::from("foo"), Symbol::new("foo"));
+        assert_ne!(Symbol::new("foo"), "equal-unknown");
+        assert_eq!(Symbol::get("equal-unknown"), None);
+
+        let mut map = HashMap::new();
+        map.insert(Symbol::new("foo"), 42);
+        assert_eq!(map.get(&Symbol::new("foo")), Some(&42));
+        assert_eq!(map.get(&Symbol::new("bar")), None);
+        assert_eq!(map.get("foo"), Some(&42));
+        assert_eq!(map.get("equal-unknown"), None);
+    }
+
+    #[cfg(feature = "unicode-folding")]
+    #[test]
+    fn folded() {
+        let one = Symbol::new("Folded-Straße");
+        assert_eq!(one.folded(), "folded-strasse");
+        assert_eq!(Symbol::new("FOLDED-STRASSE").folded(), one.folded());
+        assert_eq!(Symbol::new_folded("FOLDED-STRAẞE"), one.folded());
+        assert_eq!(Symbol::new_folded("folded-foo"), "folded-foo");
+        assert_eq!(one.folded().folded(), one.folded());
+    }
+
+    #[test]
+    fn from_fmt() {
+        let symbol = Symbol::from_fmt(format_args!("{}:{}", "from", "fmt"));
+        assert_eq!(symbol, "from:fmt");
+        assert_eq!(Symbol::from_fmt(format_args!("from:{}", "fmt")), symbol);
+        assert_eq!(Symbol::from_fmt(format_args!("from:fmt")), symbol);
+    }
+
+    #[test]
+    fn from_utf16() {
+        let value = "from-utf16-ö".encode_utf16().collect::<Vec<_>>();
+        let symbol = Symbol::from_utf16(&value).unwrap();
+        assert_eq!(symbol, "from-utf16-ö");
+        assert_eq!(Symbol::from_utf16(&value), Ok(symbol));
+        assert!(Symbol::from_utf16(&[0xd800]).is_err());
+        assert!(matches!(
+            Symbol::try_from_utf16(&[0xd800]),
+            Err(FromUtf16Error::Decode(_)),
+        ));
+        assert_eq!(Symbol::try_from_utf16(&value), Ok(symbol));
+    }
+
+    #[test]
+    fn from_utf8_lossy() {
+        let symbol = Symbol::from_utf8_lossy(b"from-utf8-\xff-lossy");
+        assert_eq!(symbol, "from-utf8-\u{fffd}-lossy");
+        assert_eq!(Symbol::from_utf8_lossy(b"from-utf8-\xfe-lossy"), symbol);
+        assert_eq!(Symbol::from_utf8_lossy(b"from-utf8"), "from-utf8");
+    }
+
+    #[test]
+    fn get() {
+        assert_eq!(Symbol::get("get-foo"), None);
+        let one = Symbol::new("get-foo");
+        assert_eq!(Symbol::get("get-foo"), Some(one));
+        assert_eq!(Symbol::get("get-bar"), None);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn grow() {
+        let values = (0..10_000).map(|i| format!("grow-{i}")).collect::<Vec<_>>();
+        let symbols = values.iter().map(Symbol::new).collect::<Vec<_>>();
+        for (value, symbol) in values.iter().zip(&symbols) {
+            assert_eq!(Symbol::get(value), Some(*symbol));
+            assert_eq!(symbol.as_static_str(), value);
         }
+    }
 
-        #[inline]
-        fn visit_string<T>(self, value: String) -> Result<Self::Value, T>
-        where
-            T: serde::de::Error,
-        {
-            Ok(super::Symbol::new(value))
+    #[test]
+    fn hash() {
+        use super::SymbolHashMap;
+
+        let mut map = SymbolHashMap::default();
+        map.insert(Symbol::new("hash-foo"), 42);
+        map.insert(Symbol::new("hash-bar"), 69);
+        assert_eq!(map.get(&Symbol::new("hash-foo")), Some(&42));
+        assert_eq!(map.get("hash-bar"), Some(&69));
+        assert_eq!(map.get("hash-baz"), None);
+
+        let mut map = super::SymbolIdMap::default();
+        map.insert(Symbol::new("hash-foo").into(), 42);
+        assert_eq!(
+            map.get(&super::SymbolId(Symbol::new("hash-foo"))),
+            Some(&42)
+        );
+        assert_eq!(map.get(&super::SymbolId(Symbol::new("hash-bar"))), None);
+    }
+
+    #[test]
+    fn id() {
+        let one = Symbol::new("id-foo");
+        assert_eq!(Symbol::from_id(one.id()), Some(one));
+        assert_eq!(Symbol::from_id(usize::MAX), None);
+        assert_eq!(Symbol::try_from_id(one.id()), Ok(one));
+        assert_eq!(
+            Symbol::try_from_id(usize::MAX),
+            Err(super::InternError::InvalidId),
+        );
+    }
+
+    #[cfg(feature = "indexmap")]
+    #[test]
+    fn index() {
+        use super::SymbolIndexMap;
+
+        let mut map = SymbolIndexMap::default();
+        map.insert(Symbol::new("index-foo"), 42);
+        map.insert(Symbol::new("index-bar"), 69);
+        assert_eq!(map.get("index-foo"), Some(&42));
+        assert_eq!(map.get_index_of("index-bar"), Some(1));
+        assert_eq!(map.get("index-baz"), None);
+        assert_eq!(map.swap_remove("index-foo"), Some(42));
+    }
+
+    #[test]
+    fn large() {
+        let value = "large".repeat(super::ARENA);
+        assert_eq!(Symbol::new(value.as_str()).as_static_str(), value);
+        assert_eq!(Symbol::new(value.clone()), Symbol::new(value));
+    }
+
+    #[test]
+    fn length() {
+        let one = Symbol::new("length");
+        assert_eq!(one.len(), 6);
+        assert!(!one.is_empty());
+        assert!(Symbol::EMPTY.is_empty());
+    }
+
+    #[test]
+    fn locate() {
+        use super::Values;
+
+        assert_eq!(Values::locate(0), Some((0, 0)));
+        assert_eq!(Values::locate(31), Some((0, 31)));
+        assert_eq!(Values::locate(32), Some((1, 0)));
+        assert_eq!(Values::locate(95), Some((1, 63)));
+        assert_eq!(Values::locate(96), Some((2, 0)));
+        assert_eq!(Values::locate(usize::MAX), None);
+    }
+
+    #[test]
+    fn new_all() {
+        let symbols = Symbol::new_all(["new-all-foo", "new-all-bar", "new-all-foo", ""]);
+        assert_eq!(symbols, ["new-all-foo", "new-all-bar", "new-all-foo", ""]);
+        assert_eq!(symbols[0], symbols[2]);
+        assert_eq!(symbols[3], Symbol::EMPTY);
+    }
+
+    #[test]
+    fn new_with() {
+        let symbol = Symbol::new_with("new-with", || "new-with".to_string());
+        assert_eq!(symbol, "new-with");
+        let other = Symbol::new_with("new-with", || unreachable!());
+        assert_eq!(other, symbol);
+        assert_eq!(Symbol::new_with("", || unreachable!()), Symbol::EMPTY);
+    }
+
+    #[test]
+    fn order() {
+        let one = Symbol::new("order-b");
+        let other = Symbol::new("order-a");
+        assert!(one > other);
+        assert!(one.cmp_by_id(&other).is_lt());
+
+        let mut symbols = vec![one, other];
+        symbols.sort();
+        assert_eq!(symbols, [other, one]);
+        symbols.sort_by(Symbol::cmp_by_id);
+        assert_eq!(symbols, [one, other]);
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!("parse".parse::<Symbol>().unwrap(), "parse");
+    }
+
+    #[cfg(not(feature = "wide"))]
+    #[test]
+    #[should_panic(expected = "the identifier is out of range")]
+    fn reserved() {
+        Symbol::reserved(u32::MAX as usize);
+    }
+
+    #[test]
+    fn resolve() {
+        let symbols = [Symbol::new("resolve-foo"), Symbol::EMPTY];
+        assert_eq!(Symbol::resolve_many(&symbols), ["resolve-foo", ""]);
+        let symbols = symbols.into_iter().rev();
+        assert_eq!(Symbol::resolve_iter(symbols), ["", "resolve-foo"]);
+    }
+
+    #[test]
+    fn shard() {
+        use std::hash::BuildHasher;
+
+        let hasher = super::Hasher::default();
+        let mut shard = super::Shard::default();
+        let hash = hasher.hash_one("shard-0");
+        for index in 0..1000 {
+            let value = Symbol::new(format!("shard-{index}")).as_static_str();
+            if let Err(entry) = shard.entry(hasher.hash_one(value), value, &hasher) {
+                entry.insert((value, Symbol::EMPTY));
+            }
+            assert!(shard.entry(hash, "shard-0", &hasher).is_ok());
         }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::Symbol;
+    #[test]
+    fn size() {
+        use std::mem::size_of;
+
+        #[cfg(not(feature = "wide"))]
+        assert_eq!(size_of::<Option<Symbol>>(), 4);
+        #[cfg(feature = "wide")]
+        assert_eq!(size_of::<Option<Symbol>>(), 8);
+    }
+
+    #[test]
+    fn static_new() {
+        let value = "static-new";
+        let one = Symbol::new_static(value);
+        #[cfg(not(feature = "cstr"))]
+        assert_eq!(one.as_static_str().as_ptr(), value.as_ptr());
+        assert_eq!(Symbol::new(String::from(value)), one);
+        assert_eq!(symbol!("static-new"), one);
+    }
+
+    #[test]
+    fn static_str() {
+        let value: &'static str = Symbol::new(String::from("static")).as_static_str();
+        assert_eq!(value, "static");
+    }
 
     #[test]
     fn format() {
         assert_eq!(format!("{}", Symbol::new("foo")), "foo");
+        assert_eq!(format!("{:?}", Symbol::new("foo")), r#""foo""#);
+        assert_eq!(format!("{:#}", Symbol::EMPTY), r#"Symbol(0: "")"#);
+        assert_eq!(format!("{:#?}", Symbol::EMPTY), r#"Symbol(0: "")"#);
+    }
+
+    #[test]
+    fn stats() {
+        let value = String::from("stats");
+        let one = super::stats();
+        Symbol::new(value.as_str());
+        Symbol::new(value.as_str());
+        let other = super::stats();
+        assert!(other.symbols > one.symbols);
+        assert!(other.bytes >= one.bytes + value.len());
+        assert!(other.saved >= one.saved + value.len());
+        assert!(other.overhead > 0);
+    }
+
+    #[test]
+    fn symbols() {
+        crate::symbols! {
+            /// A symbol.
+            FOO = "symbols-foo",
+            BAR = "symbols-bar"
+        }
+
+        assert!(!Symbol::is_interned("symbols-bar"));
+        assert_eq!(*FOO, Symbol::new("symbols-foo"));
+        assert_eq!(BAR.get(), "symbols-bar");
+    }
+
+    #[test]
+    fn wire() {
+        let one = Symbol::new("wire-foo");
+        let count = Symbol::count();
+        let bytes = one.to_wire().unwrap();
+        assert_eq!(Symbol::from_wire(bytes, count), Some(one));
+        assert_eq!(Symbol::from_wire(bytes, one.id()), None);
+        let fingerprint = Symbol::fingerprint(count).unwrap();
+        assert_eq!(Symbol::fingerprint(count), Some(fingerprint));
+        assert_eq!(Symbol::fingerprint(1), Symbol::fingerprint(1));
+        assert_ne!(Symbol::fingerprint(1), Symbol::fingerprint(count));
+        assert_eq!(Symbol::fingerprint(usize::MAX), None);
+    }
+
+    #[test]
+    fn with() {
+        let one = Symbol::new("with");
+        assert_eq!(one.with(str::len), 4);
+        assert_eq!(one.with(|value| Symbol::new(value.to_uppercase())), "WITH");
     }
 }
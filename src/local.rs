@@ -0,0 +1,411 @@
+//! Symbols confined to a thread.
+
+use core::cell::RefCell;
+use core::hash::BuildHasher;
+use core::marker::PhantomData;
+use hashbrown::hash_table::{Entry, HashTable};
+
+use crate::{Arena, Hasher, InternError, Raw};
+
+thread_local! {
+    static STORAGE: RefCell<Storage> = RefCell::new(Storage::default());
+}
+
+/// A unique static string within a thread.
+///
+/// The type mirrors the interface of `Symbol` for creating, finding, and
+/// comparing instances but is backed by a storage per thread, which involves
+/// neither locks nor atomics. Consequently, instances can be sent neither to
+/// nor shared with other threads, and the storage can be neither configured
+/// nor limited.
+///
+/// ## Example
+///
+/// ```
+/// use r#box::LocalSymbol;
+///
+/// let one = LocalSymbol::new("foo");
+/// let other = LocalSymbol::new("foo");
+/// assert_eq!(one.as_ptr(), other.as_ptr());
+/// ```
+#[derive(Clone, Copy)]
+pub struct LocalSymbol(Raw, PhantomData<*const ()>);
+
+struct Storage {
+    map: HashTable<(&'static str, LocalSymbol)>,
+    values: Vec<&'static str>,
+    arena: Arena,
+    hasher: Hasher,
+}
+
+impl LocalSymbol {
+    /// The empty string.
+    pub const EMPTY: Self = Self(Raw::MIN, PhantomData);
+
+    /// Create a new instance.
+    ///
+    /// The function panics if the identifiers are exhausted.
+    #[inline]
+    pub fn new<T>(value: T) -> Self
+    where
+        T: AsRef<str> + Into<String>,
+    {
+        match Self::try_new(value) {
+            Ok(symbol) => symbol,
+            Err(error) => panic!("{error}"),
+        }
+    }
+
+    /// Create a new instance from a static string without copying it.
+    ///
+    /// The function panics if the identifiers are exhausted.
+    #[inline]
+    pub fn new_static(value: &'static str) -> Self {
+        match Self::try_new_static(value) {
+            Ok(symbol) => symbol,
+            Err(error) => panic!("{error}"),
+        }
+    }
+
+    /// Create a new instance unless the identifiers are exhausted.
+    #[inline]
+    pub fn try_new<T>(value: T) -> Result<Self, InternError>
+    where
+        T: AsRef<str> + Into<String>,
+    {
+        Self::intern(value, Arena::store)
+    }
+
+    /// Create a new instance from a static string without copying it unless the
+    /// identifiers are exhausted.
+    #[inline]
+    pub fn try_new_static(value: &'static str) -> Result<Self, InternError> {
+        Self::intern(value, Arena::keep)
+    }
+
+    /// Create several new instances at once.
+    ///
+    /// The function panics if the identifiers are exhausted.
+    pub fn new_all<T>(values: T) -> Vec<Self>
+    where
+        T: IntoIterator,
+        T::Item: AsRef<str> + Into<String>,
+    {
+        match Self::try_new_all(values) {
+            Ok(symbols) => symbols,
+            Err(error) => panic!("{error}"),
+        }
+    }
+
+    /// Create several new instances at once unless the identifiers are
+    /// exhausted.
+    ///
+    /// The instances created before an error remain interned.
+    pub fn try_new_all<T>(values: T) -> Result<Vec<Self>, InternError>
+    where
+        T: IntoIterator,
+        T::Item: AsRef<str> + Into<String>,
+    {
+        values.into_iter().map(Self::try_new).collect()
+    }
+
+    fn intern<T, F>(value: T, store: F) -> Result<Self, InternError>
+    where
+        T: AsRef<str>,
+        F: FnOnce(&mut Arena, T) -> &'static str,
+    {
+        if value.as_ref().is_empty() {
+            return Ok(Self::EMPTY);
+        }
+        STORAGE.with_borrow_mut(|storage| {
+            let Storage {
+                map,
+                values,
+                arena,
+                hasher,
+            } = storage;
+            let hash = hasher.hash_one(value.as_ref());
+            let entry = map.entry(
+                hash,
+                |&(key, _)| key == value.as_ref(),
+                |&(key, _)| hasher.hash_one(key),
+            );
+            let entry = match entry {
+                Entry::Occupied(entry) => return Ok(entry.get().1),
+                Entry::Vacant(entry) => entry,
+            };
+            let symbol = Self::from_index(values.len()).ok_or(InternError::CapacityExceeded)?;
+            let value = store(arena, value);
+            values.push(value);
+            entry.insert((value, symbol));
+            Ok(symbol)
+        })
+    }
+
+    /// Find an existing instance by its identifier.
+    ///
+    /// The identifier is validated against the current size of the storage.
+    #[inline]
+    pub fn from_id(id: usize) -> Option<Self> {
+        if id < Self::count() {
+            Self::from_index(id)
+        } else {
+            None
+        }
+    }
+
+    /// Find an existing instance by its identifier or fail with
+    /// `InternError::InvalidId`.
+    #[inline]
+    pub fn try_from_id(id: usize) -> Result<Self, InternError> {
+        Self::from_id(id).ok_or(InternError::InvalidId)
+    }
+
+    /// Find an existing instance without creating one.
+    #[inline]
+    pub fn get(value: &str) -> Option<Self> {
+        STORAGE.with_borrow(|storage| {
+            let hash = storage.hasher.hash_one(value);
+            let entry = storage.map.find(hash, |&(key, _)| key == value);
+            entry.map(|&(_, symbol)| symbol)
+        })
+    }
+
+    /// Iterate over all instances in the order of creation.
+    ///
+    /// The iterator covers the instances that exist at the time of the call.
+    #[inline]
+    pub fn all() -> impl Iterator<Item = Self> {
+        (0..Self::count()).filter_map(Self::from_index)
+    }
+
+    /// Iterate over all underlying strings in the order of creation.
+    #[inline]
+    pub fn all_strs() -> impl Iterator<Item = &'static str> {
+        Self::all().map(|symbol| symbol.as_static_str())
+    }
+
+    /// Return the number of instances, including the empty one.
+    #[inline]
+    pub fn count() -> usize {
+        STORAGE.with_borrow(|storage| storage.values.len())
+    }
+
+    /// Check if a string has already been stored.
+    #[inline]
+    pub fn is_interned(value: &str) -> bool {
+        Self::get(value).is_some()
+    }
+
+    /// Compare with another instance by the order of creation.
+    ///
+    /// Unlike `Ord`, which is lexicographic, the comparison does not require
+    /// resolving the underlying strings.
+    #[inline]
+    pub fn cmp_by_id(&self, other: &Self) -> core::cmp::Ordering {
+        self.id().cmp(&other.id())
+    }
+
+    /// Return the identifier.
+    #[inline]
+    pub fn id(&self) -> usize {
+        (self.0.get() - 1) as usize
+    }
+
+    /// Return the length of the underlying string.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.as_static_str().len()
+    }
+
+    /// Check if the underlying string is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0 == Raw::MIN
+    }
+
+    /// Return the underlying string.
+    #[inline]
+    pub fn as_static_str(&self) -> &'static str {
+        STORAGE.with_borrow(|storage| storage.values[self.id()])
+    }
+
+    #[inline]
+    fn from_index(index: usize) -> Option<Self> {
+        let index = index.checked_add(1)?.try_into().ok()?;
+        Some(Self(Raw::new(index)?, PhantomData))
+    }
+}
+
+impl AsRef<str> for LocalSymbol {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_static_str()
+    }
+}
+
+impl Default for LocalSymbol {
+    #[inline]
+    fn default() -> Self {
+        Self::EMPTY
+    }
+}
+
+impl Default for Storage {
+    fn default() -> Self {
+        let mut storage = Self {
+            map: Default::default(),
+            values: vec![""],
+            arena: Default::default(),
+            hasher: Default::default(),
+        };
+        let hash = storage.hasher.hash_one("");
+        let entry = ("", LocalSymbol::EMPTY);
+        storage.map.insert_unique(hash, entry, |_| hash);
+        storage
+    }
+}
+
+impl From<&str> for LocalSymbol {
+    #[inline]
+    fn from(value: &str) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<String> for LocalSymbol {
+    #[inline]
+    fn from(value: String) -> Self {
+        Self::new(value)
+    }
+}
+
+impl core::borrow::Borrow<str> for LocalSymbol {
+    #[inline]
+    fn borrow(&self) -> &str {
+        self.as_static_str()
+    }
+}
+
+impl core::cmp::Eq for LocalSymbol {}
+
+impl core::cmp::Ord for LocalSymbol {
+    #[inline]
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        if self.0 == other.0 {
+            return core::cmp::Ordering::Equal;
+        }
+        self.as_static_str().cmp(other.as_static_str())
+    }
+}
+
+impl core::cmp::PartialEq for LocalSymbol {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl core::cmp::PartialEq<str> for LocalSymbol {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        self.as_static_str() == other
+    }
+}
+
+impl core::cmp::PartialEq<&str> for LocalSymbol {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        *self == **other
+    }
+}
+
+impl core::cmp::PartialEq<String> for LocalSymbol {
+    #[inline]
+    fn eq(&self, other: &String) -> bool {
+        *self == **other
+    }
+}
+
+impl core::cmp::PartialEq<LocalSymbol> for str {
+    #[inline]
+    fn eq(&self, other: &LocalSymbol) -> bool {
+        *other == *self
+    }
+}
+
+impl core::cmp::PartialEq<LocalSymbol> for &str {
+    #[inline]
+    fn eq(&self, other: &LocalSymbol) -> bool {
+        *other == **self
+    }
+}
+
+impl core::cmp::PartialEq<LocalSymbol> for String {
+    #[inline]
+    fn eq(&self, other: &LocalSymbol) -> bool {
+        *other == **self
+    }
+}
+
+impl core::cmp::PartialOrd for LocalSymbol {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl core::fmt::Debug for LocalSymbol {
+    fn fmt(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self.as_static_str(), formatter)
+    }
+}
+
+impl core::fmt::Display for LocalSymbol {
+    #[inline]
+    fn fmt(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(self.as_static_str(), formatter)
+    }
+}
+
+impl core::hash::Hash for LocalSymbol {
+    #[inline]
+    fn hash<T: core::hash::Hasher>(&self, state: &mut T) {
+        self.as_static_str().hash(state)
+    }
+}
+
+impl core::ops::Deref for LocalSymbol {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_static_str()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LocalSymbol;
+
+    #[test]
+    fn local() {
+        let one = LocalSymbol::new("local-foo");
+        assert_eq!(LocalSymbol::new("local-foo"), one);
+        assert_eq!(LocalSymbol::get("local-foo"), Some(one));
+        assert_eq!(LocalSymbol::new(""), LocalSymbol::EMPTY);
+        assert_eq!(one.as_static_str(), "local-foo");
+        assert_eq!(one, "local-foo");
+        assert_eq!(LocalSymbol::try_new("local-foo"), Ok(one));
+        assert_eq!(LocalSymbol::from_id(one.id()), Some(one));
+        assert_eq!(LocalSymbol::from_id(LocalSymbol::count()), None);
+        let symbols = LocalSymbol::new_all(["local-bar", "local-foo"]);
+        assert_eq!(symbols[1], one);
+        assert!(LocalSymbol::all().any(|symbol| symbol == symbols[0]));
+        assert!(LocalSymbol::all_strs().any(|value| value == "local-bar"));
+        assert!(LocalSymbol::is_interned("local-bar"));
+        assert!(one.cmp_by_id(&symbols[0]).is_lt());
+        let other = std::thread::spawn(|| LocalSymbol::get("local-foo").is_none());
+        assert!(other.join().unwrap());
+    }
+}
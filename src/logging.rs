@@ -0,0 +1,33 @@
+use log::kv::{Key, ToKey, ToValue, Value};
+
+use crate::Symbol;
+
+impl ToKey for Symbol {
+    #[inline]
+    fn to_key(&self) -> Key<'_> {
+        Key::from_str_static(self.as_static_str())
+    }
+}
+
+impl ToValue for Symbol {
+    #[inline]
+    fn to_value(&self) -> Value<'_> {
+        Value::from(self.as_static_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use log::kv::{Key, Source};
+
+    use crate::Symbol;
+
+    #[test]
+    fn logging() {
+        let (key, value) = (Symbol::new("logging-foo"), Symbol::new("logging-bar"));
+        let source = [(key, value)];
+        let value = source.get(Key::from_str("logging-foo")).unwrap();
+        assert_eq!(value.to_borrowed_str(), Some("logging-bar"));
+        assert!(source.get(Key::from_str("logging-bar")).is_none());
+    }
+}
@@ -0,0 +1,83 @@
+//! Reporting of metrics via the metrics facade.
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+static HITS: AtomicUsize = AtomicUsize::new(0);
+static MISSES: AtomicUsize = AtomicUsize::new(0);
+
+pub fn hit() {
+    let hits = HITS.fetch_add(1, Ordering::Relaxed) + 1;
+    ratio(hits, MISSES.load(Ordering::Relaxed));
+}
+
+pub fn miss(bytes: usize) {
+    let misses = MISSES.fetch_add(1, Ordering::Relaxed) + 1;
+    ::metrics::counter!("symbols_created_total").increment(1);
+    resize(bytes);
+    ratio(HITS.load(Ordering::Relaxed), misses);
+}
+
+pub fn resize(bytes: usize) {
+    ::metrics::gauge!("pool_bytes").set(bytes as f64);
+}
+
+fn ratio(hits: usize, misses: usize) {
+    ::metrics::gauge!("intern_hit_ratio").set(hits as f64 / (hits + misses) as f64);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    use metrics::{
+        Counter, Gauge, Histogram, Key, KeyName, Metadata, Recorder, SharedString, Unit,
+    };
+
+    use crate::Symbol;
+
+    #[derive(Default)]
+    struct Values(Mutex<HashMap<String, Arc<AtomicU64>>>);
+
+    impl Values {
+        fn get(&self, key: &str) -> Arc<AtomicU64> {
+            let mut values = self.0.lock().unwrap();
+            values.entry(key.into()).or_default().clone()
+        }
+    }
+
+    impl Recorder for Values {
+        fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+            Counter::from_arc(self.get(key.name()))
+        }
+
+        fn register_gauge(&self, key: &Key, _: &Metadata<'_>) -> Gauge {
+            Gauge::from_arc(self.get(key.name()))
+        }
+
+        fn register_histogram(&self, _: &Key, _: &Metadata<'_>) -> Histogram {
+            Histogram::noop()
+        }
+    }
+
+    #[test]
+    fn metrics() {
+        let values = Values::default();
+        metrics::with_local_recorder(&values, || {
+            Symbol::new("metrics-foo");
+            Symbol::new("metrics-foo");
+            Symbol::new_all(["metrics-bar", "metrics-baz"]);
+        });
+        let value = |key| values.get(key).load(Ordering::Relaxed);
+        assert_eq!(value("symbols_created_total"), 3);
+        assert!(f64::from_bits(value("pool_bytes")) >= 33.0);
+        assert!((0.0..=1.0).contains(&f64::from_bits(value("intern_hit_ratio"))));
+    }
+}
@@ -0,0 +1,80 @@
+use core::hash::BuildHasher;
+use rayon::prelude::*;
+
+use crate::sync::RwLock;
+use crate::{Arena, Box, Symbol, SHARDS};
+
+/// Create several symbols in parallel.
+///
+/// The strings are partitioned by shard, and each shard is processed by a
+/// separate task holding its lock once. The function panics if the limit set
+/// via `limit` is reached or the storage is frozen via `freeze`.
+pub fn par_intern<T>(values: &[T]) -> Vec<Symbol>
+where
+    T: AsRef<str> + Sync,
+{
+    let state = Box::instance();
+    if state.transforms() {
+        return values
+            .iter()
+            .map(|value| Symbol::new(value.as_ref()))
+            .collect();
+    }
+    let hashes = values
+        .par_iter()
+        .map(|value| state.hasher.hash_one(value.as_ref()));
+    let hashes = hashes.collect::<Vec<_>>();
+    let mut partitions = vec![Vec::new(); SHARDS];
+    for (index, &hash) in hashes.iter().enumerate() {
+        partitions[Box::locate(hash)].push(index);
+    }
+    state
+        .values
+        .reserve(state.values.len().saturating_add(values.len()));
+    let partitions = partitions
+        .into_par_iter()
+        .enumerate()
+        .map(|(shard, indices)| {
+            let mut shard = RwLock::write(&state.shards[shard]);
+            let mut symbols = Vec::with_capacity(indices.len());
+            for index in indices {
+                let (hash, value) = (hashes[index], values[index].as_ref());
+                let entry = shard.entry(hash, value, &state.hasher);
+                let symbol = match entry {
+                    Ok(symbol) => Ok((state.hit(symbol, value.len()), false)),
+                    Err(entry) => state
+                        .occupy(entry, &mut state.table.lock(), value, Arena::store)
+                        .map(|symbol| (symbol, true)),
+                };
+                match symbol {
+                    Ok((symbol, created)) => symbols.push((index, symbol, created)),
+                    Err(error) => panic!("{error}"),
+                }
+            }
+            symbols
+        });
+    let mut symbols = vec![Symbol::EMPTY; values.len()];
+    for (index, symbol, created) in partitions.flatten_iter().collect::<Vec<_>>() {
+        symbols[index] = symbol;
+        if created {
+            state.notify(symbol.id()..symbol.id() + 1);
+        }
+    }
+    symbols
+}
+
+#[cfg(test)]
+mod tests {
+    use super::par_intern;
+    use crate::Symbol;
+
+    #[test]
+    fn intern() {
+        let values = (0..1000).map(|index| format!("par-intern-{}", index % 100));
+        let values = values.collect::<Vec<_>>();
+        let symbols = par_intern(&values);
+        assert_eq!(symbols, values);
+        assert_eq!(symbols[42], symbols[142]);
+        assert_eq!(Symbol::new("par-intern-42"), symbols[42]);
+    }
+}
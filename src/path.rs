@@ -0,0 +1,207 @@
+//! Symbols for paths.
+
+use core::hash::BuildHasher;
+use hashbrown::hash_table::{Entry, HashTable};
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+use crate::sync::Mutex;
+use crate::{Arena, Hasher};
+
+static STORAGE: Mutex<Option<Storage>> = Mutex::new(None);
+
+/// A unique static path.
+///
+/// Paths are stored separately from strings and need not be valid UTF-8. They
+/// are deduplicated with respect to the equality of `Path`, which compares
+/// components, so that the first spelling encountered is kept.
+///
+/// ## Example
+///
+/// ```
+/// use std::path::Path;
+///
+/// use r#box::PathSymbol;
+///
+/// let one = PathSymbol::new("src/lib.rs");
+/// let other = PathSymbol::new(Path::new("src").join("lib.rs"));
+/// assert_eq!(one, other);
+/// assert_eq!(one.extension().unwrap(), "rs");
+/// ```
+#[derive(Clone, Copy)]
+pub struct PathSymbol(&'static Path);
+
+#[derive(Default)]
+struct Storage {
+    map: HashTable<PathSymbol>,
+    arena: Arena,
+    hasher: Hasher,
+}
+
+impl PathSymbol {
+    /// Create a new instance.
+    pub fn new<T: AsRef<Path>>(value: T) -> Self {
+        let value = value.as_ref();
+        let mut storage = STORAGE.lock();
+        let Storage { map, arena, hasher } = storage.get_or_insert_with(Default::default);
+        let hash = hasher.hash_one(value);
+        let entry = map.entry(
+            hash,
+            |symbol| symbol.0 == value,
+            |symbol| hasher.hash_one(symbol.0),
+        );
+        match entry {
+            Entry::Occupied(entry) => *entry.get(),
+            Entry::Vacant(entry) => {
+                let bytes = arena.store_bytes(value.as_os_str().as_encoded_bytes());
+                // SAFETY: The bytes have been copied from an `OsStr`.
+                let value = unsafe { OsStr::from_encoded_bytes_unchecked(bytes) };
+                *entry.insert(Self(Path::new(value))).get()
+            }
+        }
+    }
+
+    /// Find an existing instance without creating one.
+    pub fn get<T: AsRef<Path>>(value: T) -> Option<Self> {
+        let value = value.as_ref();
+        let storage = STORAGE.lock();
+        let storage = storage.as_ref()?;
+        let hash = storage.hasher.hash_one(value);
+        storage.map.find(hash, |symbol| symbol.0 == value).copied()
+    }
+
+    /// Return the number of instances.
+    pub fn count() -> usize {
+        STORAGE
+            .lock()
+            .as_ref()
+            .map_or(0, |storage| storage.map.len())
+    }
+
+    /// Return the underlying path.
+    #[inline]
+    pub fn as_path(&self) -> &'static Path {
+        self.0
+    }
+}
+
+impl AsRef<OsStr> for PathSymbol {
+    #[inline]
+    fn as_ref(&self) -> &OsStr {
+        self.0.as_os_str()
+    }
+}
+
+impl AsRef<Path> for PathSymbol {
+    #[inline]
+    fn as_ref(&self) -> &Path {
+        self.0
+    }
+}
+
+impl From<&Path> for PathSymbol {
+    #[inline]
+    fn from(value: &Path) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<PathBuf> for PathSymbol {
+    #[inline]
+    fn from(value: PathBuf) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<PathSymbol> for PathBuf {
+    #[inline]
+    fn from(value: PathSymbol) -> Self {
+        value.0.to_path_buf()
+    }
+}
+
+impl core::borrow::Borrow<Path> for PathSymbol {
+    #[inline]
+    fn borrow(&self) -> &Path {
+        self.0
+    }
+}
+
+impl core::cmp::Eq for PathSymbol {}
+
+impl core::cmp::Ord for PathSymbol {
+    #[inline]
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        if self == other {
+            return core::cmp::Ordering::Equal;
+        }
+        self.0.cmp(other.0)
+    }
+}
+
+impl core::cmp::PartialEq for PathSymbol {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        core::ptr::eq(self.0, other.0)
+    }
+}
+
+impl core::cmp::PartialOrd for PathSymbol {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl core::fmt::Debug for PathSymbol {
+    fn fmt(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self.0, formatter)
+    }
+}
+
+impl core::hash::Hash for PathSymbol {
+    #[inline]
+    fn hash<T: core::hash::Hasher>(&self, state: &mut T) {
+        self.0.hash(state)
+    }
+}
+
+impl core::ops::Deref for PathSymbol {
+    type Target = Path;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::PathSymbol;
+
+    #[test]
+    fn path() {
+        let one = PathSymbol::new("path/foo");
+        assert_eq!(PathSymbol::new(Path::new("path").join("foo")), one);
+        assert_eq!(PathSymbol::new("path//foo/"), one);
+        assert_eq!(PathSymbol::get("path/foo"), Some(one));
+        assert_eq!(PathSymbol::get("path/bar"), None);
+        assert_eq!(one.as_path(), Path::new("path/foo"));
+        assert_eq!(one.file_name().unwrap(), "foo");
+        assert!(PathSymbol::count() > 0);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn path_invalid() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let value = Path::new(OsStr::from_bytes(b"path/\xff"));
+        let one = PathSymbol::new(value);
+        assert_eq!(one.as_os_str().as_bytes(), b"path/\xff");
+        assert_eq!(PathSymbol::get(value), Some(one));
+    }
+}
@@ -0,0 +1,205 @@
+//! Storage separate from the global one.
+
+use alloc::string::String;
+use core::hash::BuildHasher;
+use hashbrown::HashMap;
+
+use crate::sync::{Mutex, OnceLock};
+use crate::{Arena, Box, Hasher, InternError, Symbol};
+
+/// A storage separate from the global one.
+///
+/// An instance can be created in a constant context and is initialized on
+/// first use, so that it can be placed in a static. Alternatively, an instance
+/// can be obtained by name via `named`.
+///
+/// ## Example
+///
+/// ```
+/// use r#box::Pool;
+///
+/// let pool = Pool::named("ast");
+/// let symbol = pool.intern("foo");
+/// assert!(core::ptr::eq(symbol.pool(), Pool::named("ast")));
+/// assert_eq!(symbol.as_static_str(), "foo");
+/// assert_eq!(Pool::named("plugin").get("foo"), None);
+/// ```
+pub struct Pool(OnceLock<Box>);
+
+/// A unique static string within a pool.
+///
+/// The symbol carries a reference to its pool, so that symbols from different
+/// pools are never equal.
+#[derive(Clone, Copy)]
+pub struct PoolSymbol {
+    pool: &'static Pool,
+    symbol: Symbol,
+}
+
+impl Pool {
+    /// Create a new instance.
+    #[inline]
+    pub const fn new() -> Self {
+        Self(OnceLock::new())
+    }
+
+    /// Return the instance with a name, creating it on first use.
+    pub fn named(name: &str) -> &'static Self {
+        static POOLS: Mutex<Option<HashMap<String, &'static Pool, Hasher>>> = Mutex::new(None);
+        let mut pools = POOLS.lock();
+        let pools = pools.get_or_insert_with(Default::default);
+        if let Some(pool) = pools.get(name) {
+            return pool;
+        }
+        let pool = alloc::boxed::Box::leak(alloc::boxed::Box::new(Self::new()));
+        pools.insert(name.into(), pool);
+        pool
+    }
+
+    /// Create a new symbol within the instance.
+    ///
+    /// The function panics if the instance cannot accommodate the string.
+    #[inline]
+    #[cfg_attr(feature = "debug-tracking", track_caller)]
+    pub fn intern<T>(&'static self, value: T) -> PoolSymbol
+    where
+        T: AsRef<str> + Into<String>,
+    {
+        match self.try_intern(value) {
+            Ok(symbol) => symbol,
+            Err(error) => panic!("{error}"),
+        }
+    }
+
+    /// Create a new symbol within the instance unless the instance cannot
+    /// accommodate the string.
+    #[cfg_attr(feature = "debug-tracking", track_caller)]
+    pub fn try_intern<T>(&'static self, value: T) -> Result<PoolSymbol, InternError>
+    where
+        T: AsRef<str> + Into<String>,
+    {
+        let state = self.state();
+        let hash = state.hasher.hash_one(value.as_ref());
+        let symbol = state.intern(hash, value, Arena::store)?;
+        Ok(PoolSymbol { pool: self, symbol })
+    }
+
+    /// Find an existing symbol within the instance without creating one.
+    #[inline]
+    pub fn get(&'static self, value: &str) -> Option<PoolSymbol> {
+        let state = self.state();
+        let hash = state.hasher.hash_one(value);
+        let symbol = state.find(hash, value)?;
+        Some(PoolSymbol { pool: self, symbol })
+    }
+
+    /// Return the number of strings stored, including the empty one.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.state().values.len()
+    }
+
+    /// Check if no strings are stored apart from the empty one.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 1
+    }
+
+    #[inline]
+    pub(crate) fn state(&self) -> &Box {
+        self.0.get_or_init(Default::default)
+    }
+}
+
+impl PoolSymbol {
+    /// Return the pool.
+    #[inline]
+    pub fn pool(&self) -> &'static Pool {
+        self.pool
+    }
+
+    /// Return the identifier within the pool.
+    #[inline]
+    pub fn id(&self) -> usize {
+        self.symbol.id()
+    }
+
+    /// Return the underlying string.
+    #[inline]
+    pub fn as_static_str(&self) -> &'static str {
+        self.pool.state().values.get(self.id()).unwrap()
+    }
+}
+
+impl AsRef<str> for PoolSymbol {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_static_str()
+    }
+}
+
+impl Default for Pool {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl core::cmp::Eq for PoolSymbol {}
+
+impl core::cmp::PartialEq for PoolSymbol {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        core::ptr::eq(self.pool, other.pool) && self.symbol == other.symbol
+    }
+}
+
+impl core::fmt::Debug for PoolSymbol {
+    fn fmt(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self.as_static_str(), formatter)
+    }
+}
+
+impl core::fmt::Display for PoolSymbol {
+    #[inline]
+    fn fmt(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(self.as_static_str(), formatter)
+    }
+}
+
+impl core::hash::Hash for PoolSymbol {
+    #[inline]
+    fn hash<T: core::hash::Hasher>(&self, state: &mut T) {
+        self.as_static_str().hash(state)
+    }
+}
+
+impl core::ops::Deref for PoolSymbol {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_static_str()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Pool;
+
+    #[test]
+    fn named() {
+        let one = Pool::named("one");
+        let other = Pool::named("other");
+        assert!(std::ptr::eq(Pool::named("one"), one));
+        assert!(!std::ptr::eq(one, other));
+        let symbol = one.intern("pool-foo");
+        assert_ne!(symbol, other.intern("pool-foo"));
+        assert_eq!(one.intern("pool-foo"), symbol);
+        assert_eq!(one.get("pool-foo"), Some(symbol));
+        assert_eq!(one.get("pool-bar"), None);
+        assert_eq!(symbol.id(), 1);
+        assert_eq!(&*symbol, "pool-foo");
+        assert_eq!(one.len(), 2);
+    }
+}
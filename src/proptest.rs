@@ -0,0 +1,93 @@
+//! Strategies for property testing via proptest.
+//!
+//! ## Example
+//!
+//! ```
+//! use proptest::prelude::*;
+//! use r#box::Symbol;
+//!
+//! proptest!(|(symbol in r#box::proptest::symbol())| {
+//!     prop_assert_eq!(Symbol::new(symbol.as_static_str()), symbol);
+//! });
+//! ```
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::{Range, RangeInclusive};
+
+use ::proptest::arbitrary::Arbitrary;
+use ::proptest::strategy::{BoxedStrategy, Strategy};
+
+use crate::Symbol;
+
+/// The parameters of generation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Parameters {
+    /// The ranges of characters to draw from.
+    pub class: &'static [RangeInclusive<char>],
+    /// The range of the number of characters.
+    pub length: Range<usize>,
+}
+
+impl Default for Parameters {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            class: &['a'..='z', 'A'..='Z', '0'..='9', '_'..='_'],
+            length: 0..16,
+        }
+    }
+}
+
+/// Return a strategy generating symbols with the default parameters.
+#[inline]
+pub fn symbol() -> BoxedStrategy<Symbol> {
+    symbol_with(Parameters::default())
+}
+
+/// Return a strategy generating symbols with custom parameters.
+///
+/// Generated symbols shrink toward shorter strings and toward the first
+/// character of the first range.
+pub fn symbol_with(parameters: Parameters) -> BoxedStrategy<Symbol> {
+    let characters = ::proptest::char::ranges(parameters.class.into());
+    ::proptest::collection::vec(characters, parameters.length)
+        .prop_map(|characters: Vec<char>| Symbol::new(characters.into_iter().collect::<String>()))
+        .boxed()
+}
+
+impl Arbitrary for Symbol {
+    type Parameters = Parameters;
+    type Strategy = BoxedStrategy<Symbol>;
+
+    #[inline]
+    fn arbitrary_with(parameters: Self::Parameters) -> Self::Strategy {
+        symbol_with(parameters)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::arbitrary::any_with;
+    use proptest::strategy::{Strategy, ValueTree};
+    use proptest::test_runner::TestRunner;
+
+    use super::Parameters;
+
+    #[test]
+    fn symbol() {
+        let mut runner = TestRunner::deterministic();
+        let strategy = any_with::<crate::Symbol>(Parameters {
+            class: &['x'..='y'],
+            length: 1..4,
+        });
+        for _ in 0..100 {
+            let mut tree = strategy.new_tree(&mut runner).unwrap();
+            let symbol = tree.current();
+            assert!((1..4).contains(&symbol.len()));
+            assert!(symbol.chars().all(|character| "xy".contains(character)));
+            while tree.simplify() {}
+            assert_eq!(tree.current(), "x");
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use alloc::string::ToString;
+use core::convert::Infallible;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::pybacked::PyBackedStr;
+use pyo3::types::PyString;
+
+use crate::Symbol;
+
+impl<'py> IntoPyObject<'py> for Symbol {
+    type Target = PyString;
+    type Output = Bound<'py, PyString>;
+    type Error = Infallible;
+
+    #[inline]
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        self.as_static_str().into_pyobject(py)
+    }
+}
+
+impl<'py> IntoPyObject<'py> for &Symbol {
+    type Target = PyString;
+    type Output = Bound<'py, PyString>;
+    type Error = Infallible;
+
+    #[inline]
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        self.as_static_str().into_pyobject(py)
+    }
+}
+
+impl FromPyObject<'_, '_> for Symbol {
+    type Error = PyErr;
+
+    fn extract(object: Borrowed<'_, '_, PyAny>) -> PyResult<Self> {
+        let value = object.extract::<PyBackedStr>()?;
+        Symbol::try_new(&*value).map_err(|error| PyValueError::new_err(error.to_string()))
+    }
+}
+
+/// A symbol exposed to Python as a class.
+///
+/// It is constructed from a string and exposes the identifier and the value
+/// of the symbol.
+#[pyclass(name = "Symbol", module = "box", frozen, eq, hash, str, from_py_object)]
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct PySymbol(pub Symbol);
+
+#[pymethods]
+impl PySymbol {
+    #[new]
+    fn new(value: Symbol) -> Self {
+        Self(value)
+    }
+
+    /// The identifier.
+    #[getter]
+    fn id(&self) -> usize {
+        self.0.id()
+    }
+
+    /// The value.
+    #[getter]
+    fn value(&self) -> &'static str {
+        self.0.as_static_str()
+    }
+
+    fn __repr__(&self) -> alloc::string::String {
+        alloc::format!("Symbol({:?})", self.0.as_static_str())
+    }
+}
+
+impl core::fmt::Display for PySymbol {
+    #[inline]
+    fn fmt(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.0.fmt(formatter)
+    }
+}
+
+impl From<Symbol> for PySymbol {
+    #[inline]
+    fn from(symbol: Symbol) -> Self {
+        Self(symbol)
+    }
+}
+
+impl From<PySymbol> for Symbol {
+    #[inline]
+    fn from(symbol: PySymbol) -> Self {
+        symbol.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pyo3::prelude::*;
+    use pyo3::types::PyDict;
+
+    use super::PySymbol;
+    use crate::Symbol;
+
+    #[test]
+    fn python() {
+        Python::initialize();
+        Python::attach(|py| {
+            let one = Symbol::new("python-foo");
+            let object = one.into_pyobject(py).unwrap();
+            assert_eq!(object.extract::<&str>().unwrap(), "python-foo");
+            assert_eq!(object.extract::<Symbol>().unwrap(), one);
+            assert!(1i32.into_pyobject(py).unwrap().extract::<Symbol>().is_err());
+
+            let locals = PyDict::new(py);
+            locals
+                .set_item("Symbol", py.get_type::<PySymbol>())
+                .unwrap();
+            let symbol = py
+                .eval(c"Symbol('python-foo')", None, Some(&locals))
+                .unwrap();
+            assert_eq!(symbol.extract::<PySymbol>().unwrap(), PySymbol(one));
+            assert_eq!(
+                symbol.getattr("id").unwrap().extract::<usize>().unwrap(),
+                one.id()
+            );
+            assert_eq!(symbol.str().unwrap().to_str().unwrap(), "python-foo");
+            assert_eq!(
+                symbol.repr().unwrap().to_str().unwrap(),
+                "Symbol(\"python-foo\")"
+            );
+        });
+    }
+}
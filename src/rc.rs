@@ -0,0 +1,188 @@
+//! Symbols released once unused.
+
+use alloc::sync::{Arc, Weak};
+use core::hash::BuildHasher;
+use core::mem::ManuallyDrop;
+use hashbrown::hash_table::{Entry, HashTable};
+
+use crate::sync::Mutex;
+use crate::Hasher;
+
+static TABLE: Mutex<Option<Table>> = Mutex::new(None);
+
+/// A unique string released once the last instance is dropped.
+///
+/// Unlike `Symbol`, whose strings live until the process exits, the strings
+/// are reference-counted, which suits unbounded streams of mostly transient
+/// strings. Creating and dropping instances takes a lock, and comparing them
+/// does not.
+///
+/// ## Example
+///
+/// ```
+/// use r#box::RcSymbol;
+///
+/// let one = RcSymbol::new("foo");
+/// assert_eq!(RcSymbol::new("foo"), one);
+/// assert_eq!(RcSymbol::count(), 1);
+/// drop(one);
+/// assert_eq!(RcSymbol::count(), 0);
+/// ```
+#[derive(Clone)]
+pub struct RcSymbol(ManuallyDrop<Arc<str>>);
+
+#[derive(Default)]
+struct Table {
+    map: HashTable<(u64, Weak<str>)>,
+    hasher: Hasher,
+}
+
+impl RcSymbol {
+    /// Create a new instance.
+    pub fn new(value: &str) -> Self {
+        let mut table = TABLE.lock();
+        let Table { map, hasher } = table.get_or_insert_with(Default::default);
+        let hash = hasher.hash_one(value);
+        let entry = map.entry(
+            hash,
+            |(_, other)| other.upgrade().is_some_and(|other| &*other == value),
+            |&(hash, _)| hash,
+        );
+        let entry = match entry {
+            Entry::Occupied(entry) => {
+                if let Some(value) = entry.get().1.upgrade() {
+                    return Self(ManuallyDrop::new(value));
+                }
+                entry.remove().1
+            }
+            Entry::Vacant(entry) => entry,
+        };
+        let value: Arc<str> = value.into();
+        entry.insert((hash, Arc::downgrade(&value)));
+        Self(ManuallyDrop::new(value))
+    }
+
+    /// Return the number of strings alive.
+    pub fn count() -> usize {
+        TABLE.lock().as_ref().map_or(0, |table| table.map.len())
+    }
+
+    /// Return the underlying string.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for RcSymbol {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Drop for RcSymbol {
+    fn drop(&mut self) {
+        // SAFETY: The field is not used after being taken.
+        let value = unsafe { ManuallyDrop::take(&mut self.0) };
+        let mut table = TABLE.lock();
+        let Some(Table { map, hasher }) = table.as_mut() else {
+            return;
+        };
+        let hash = hasher.hash_one(&*value);
+        let weak = Arc::downgrade(&value);
+        // The count is checked after the decrement, so that the last of the
+        // instances dropped concurrently removes the entry, and instances are
+        // only created with the lock held, so that the count cannot grow in
+        // the meantime.
+        drop(value);
+        if weak.strong_count() > 0 {
+            return;
+        }
+        let eq = |(_, other): &(u64, Weak<str>)| Weak::ptr_eq(other, &weak);
+        if let Ok(entry) = map.find_entry(hash, eq) {
+            entry.remove();
+        }
+    }
+}
+
+impl core::borrow::Borrow<str> for RcSymbol {
+    #[inline]
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl core::cmp::Eq for RcSymbol {}
+
+impl core::cmp::Ord for RcSymbol {
+    #[inline]
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        if Arc::ptr_eq(&self.0, &other.0) {
+            return core::cmp::Ordering::Equal;
+        }
+        self.0.cmp(&other.0)
+    }
+}
+
+impl core::cmp::PartialEq for RcSymbol {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl core::cmp::PartialOrd for RcSymbol {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl core::fmt::Debug for RcSymbol {
+    fn fmt(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&*self.0, formatter)
+    }
+}
+
+impl core::fmt::Display for RcSymbol {
+    #[inline]
+    fn fmt(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(&*self.0, formatter)
+    }
+}
+
+impl core::hash::Hash for RcSymbol {
+    #[inline]
+    fn hash<T: core::hash::Hasher>(&self, state: &mut T) {
+        self.0.hash(state)
+    }
+}
+
+impl core::ops::Deref for RcSymbol {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RcSymbol;
+
+    #[test]
+    fn release() {
+        let one = RcSymbol::new("rc-foo");
+        let other = one.clone();
+        assert_eq!(RcSymbol::new("rc-foo"), one);
+        assert_ne!(RcSymbol::new("rc-bar"), one);
+        let pointer = one.as_ptr();
+        drop(one);
+        assert_eq!(RcSymbol::new("rc-foo").as_ptr(), pointer);
+        drop(other);
+        assert!(RcSymbol::new("rc-foo") != RcSymbol::new("rc-baz"));
+        assert_eq!(RcSymbol::new("rc-foo").as_str(), "rc-foo");
+    }
+}
@@ -0,0 +1,41 @@
+use alloc::borrow::Cow;
+
+use schemars::{JsonSchema, Schema, SchemaGenerator};
+
+use crate::Symbol;
+
+impl JsonSchema for Symbol {
+    #[inline]
+    fn inline_schema() -> bool {
+        true
+    }
+
+    #[inline]
+    fn schema_name() -> Cow<'static, str> {
+        <str as JsonSchema>::schema_name()
+    }
+
+    #[inline]
+    fn schema_id() -> Cow<'static, str> {
+        <str as JsonSchema>::schema_id()
+    }
+
+    #[inline]
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        <str as JsonSchema>::json_schema(generator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Symbol;
+
+    #[test]
+    fn schema() {
+        assert_eq!(schemars::schema_for!(Symbol), schemars::schema_for!(str));
+        assert_eq!(
+            schemars::schema_for!([Symbol; 2]),
+            schemars::schema_for!([String; 2]),
+        );
+    }
+}
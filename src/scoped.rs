@@ -0,0 +1,193 @@
+//! Storage confined to a scope.
+
+use alloc::borrow::Cow;
+use core::marker::PhantomData;
+
+use crate::{Backend, Interner};
+
+/// Run a function with a storage that is dropped once the function returns.
+///
+/// The symbols created within the scope are branded with a lifetime unique to
+/// the scope, so that they can neither escape it nor be resolved by another
+/// scope.
+///
+/// ## Example
+///
+/// ```
+/// let length = r#box::scoped(|scope| {
+///     let one = scope.intern("foo");
+///     assert_eq!(scope.intern("foo"), one);
+///     scope.resolve(one).len()
+/// });
+/// assert_eq!(length, 3);
+/// ```
+///
+/// Symbols cannot escape:
+///
+/// ```compile_fail
+/// let symbol = r#box::scoped(|scope| scope.intern("foo"));
+/// ```
+#[inline]
+pub fn scoped<F, T>(function: F) -> T
+where
+    F: for<'scope> FnOnce(&mut Scope<'scope>) -> T,
+{
+    scoped_with(Interner::new(), function)
+}
+
+/// Run a function with a given storage that is dropped once the function
+/// returns.
+///
+/// The function is the same as `scoped` but allows for choosing the storage.
+pub fn scoped_with<B, F, T>(backend: B, function: F) -> T
+where
+    B: Backend,
+    F: for<'scope> FnOnce(&mut Scope<'scope, B>) -> T,
+{
+    function(&mut Scope {
+        interner: backend,
+        brand: PhantomData,
+    })
+}
+
+/// A storage confined to a scope.
+pub struct Scope<'scope, B = Interner> {
+    interner: B,
+    brand: Brand<'scope>,
+}
+
+/// A unique string within a scope.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ScopedSymbol<'scope> {
+    id: usize,
+    brand: Brand<'scope>,
+}
+
+// The lifetime is invariant, so that brands of different scopes are never
+// compatible.
+type Brand<'scope> = PhantomData<fn(&'scope ()) -> &'scope ()>;
+
+impl<'scope, B: Backend> Scope<'scope, B> {
+    /// Create a new symbol.
+    #[inline]
+    pub fn intern(&mut self, value: &str) -> ScopedSymbol<'scope> {
+        ScopedSymbol {
+            id: self.interner.intern(value),
+            brand: PhantomData,
+        }
+    }
+
+    /// Find an existing symbol without creating one.
+    #[inline]
+    pub fn get(&self, value: &str) -> Option<ScopedSymbol<'scope>> {
+        let id = self.interner.get(value)?;
+        Some(ScopedSymbol {
+            id,
+            brand: PhantomData,
+        })
+    }
+
+    /// Return the string of a symbol.
+    #[inline]
+    pub fn resolve(&self, symbol: ScopedSymbol<'scope>) -> Cow<'_, str> {
+        self.interner.resolve(symbol.id).unwrap()
+    }
+
+    /// Return the number of strings stored.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.interner.len()
+    }
+
+    /// Check if no strings are stored.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.interner.is_empty()
+    }
+}
+
+impl ScopedSymbol<'_> {
+    /// Return the identifier within the scope.
+    #[inline]
+    pub fn id(&self) -> usize {
+        self.id
+    }
+}
+
+impl core::fmt::Debug for ScopedSymbol<'_> {
+    fn fmt(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        formatter
+            .debug_tuple("ScopedSymbol")
+            .field(&self.id)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::borrow::Cow;
+
+    use super::{scoped, scoped_with};
+    use crate::front::FrontCoded;
+    use crate::Backend;
+
+    #[derive(Default)]
+    struct Linear(Vec<String>);
+
+    impl Backend for Linear {
+        fn intern(&mut self, value: &str) -> usize {
+            self.get(value).unwrap_or_else(|| {
+                self.0.push(value.into());
+                self.0.len() - 1
+            })
+        }
+
+        fn get(&self, value: &str) -> Option<usize> {
+            self.0.iter().position(|other| other == value)
+        }
+
+        fn resolve(&self, id: usize) -> Option<Cow<'_, str>> {
+            self.0.get(id).map(|value| Cow::Borrowed(value.as_str()))
+        }
+
+        fn len(&self) -> usize {
+            self.0.len()
+        }
+    }
+
+    #[test]
+    fn front() {
+        let value = scoped_with(FrontCoded::new('.'), |scope| {
+            let one = scope.intern("service.host");
+            assert_eq!(scope.intern("service.host"), one);
+            assert_eq!(scope.get("service"), None);
+            scope.resolve(one).into_owned()
+        });
+        assert_eq!(value, "service.host");
+    }
+
+    #[test]
+    fn scope() {
+        let count = scoped(|scope| {
+            let one = scope.intern("foo");
+            let other = scope.intern("bar");
+            assert_ne!(one, other);
+            assert_eq!(scope.get("foo"), Some(one));
+            assert_eq!(scope.get("baz"), None);
+            assert_eq!(scope.resolve(other), "bar");
+            scope.len()
+        });
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn scope_with() {
+        let value = scoped_with(Linear::default(), |scope| {
+            let one = scope.intern("foo");
+            assert_eq!(scope.intern("foo"), one);
+            assert_eq!(scope.len(), 1);
+            scope.resolve(one).to_string()
+        });
+        assert_eq!(value, "foo");
+    }
+}
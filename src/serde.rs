@@ -0,0 +1,554 @@
+//! Serialization via serde.
+//!
+//! Symbols are serialized as strings. Containers with many repeated symbols
+//! can be serialized via `with_table`, and individual symbols can be
+//! serialized as identifiers via `with_id`, in which case a `Table` has to
+//! accompany them.
+
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt::Formatter;
+
+use ::serde::de::{Deserialize, DeserializeSeed, Deserializer, Error, SeqAccess, Visitor};
+use ::serde::ser::{Serialize, SerializeSeq, Serializer};
+
+use crate::{Backend, Interner, Symbol};
+
+/// A table of strings for reconstructing symbols serialized as identifiers.
+///
+/// The table contains all instances in the order of their identifiers and is
+/// serialized as a sequence of strings, which are interned when it is
+/// deserialized.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Table(Arc<[Symbol]>);
+
+/// A seed for deserializing strings into a storage other than the global one.
+///
+/// The deserialized value is the identifier of the string in the storage.
+///
+/// ## Example
+///
+/// ```
+/// use r#box::serde::InternerSeed;
+/// use r#box::Interner;
+/// use serde::de::DeserializeSeed;
+///
+/// let mut interner = Interner::new();
+/// let mut deserializer = serde_json::Deserializer::from_str(r#""foo""#);
+/// let id = InternerSeed::new(&mut interner).deserialize(&mut deserializer).unwrap();
+/// assert_eq!(interner.resolve(id), Some("foo"));
+/// ```
+pub struct InternerSeed<'l, B = Interner>(&'l mut B);
+
+struct Entry(Symbol);
+
+struct SymbolVisitor;
+
+struct TableVisitor;
+
+impl Table {
+    /// Capture all instances created so far.
+    pub fn new() -> Self {
+        Self((0..Symbol::count()).filter_map(Symbol::from_id).collect())
+    }
+
+    /// Return the instance corresponding to an identifier of the process that
+    /// has produced the table.
+    #[inline]
+    pub fn get(&self, id: usize) -> Option<Symbol> {
+        self.0.get(id).copied()
+    }
+
+    /// Return the number of instances.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Check if there are no instances.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Run a function in which instances deserialized from identifiers on the
+    /// current thread are resolved via the table.
+    #[cfg(feature = "std")]
+    pub fn with<F, T>(&self, function: F) -> T
+    where
+        F: FnOnce() -> T,
+    {
+        struct Guard(Option<Table>);
+
+        impl Drop for Guard {
+            fn drop(&mut self) {
+                ACTIVE.with(|active| *active.borrow_mut() = self.0.take());
+            }
+        }
+
+        let _guard = Guard(ACTIVE.with(|active| active.replace(Some(self.clone()))));
+        function()
+    }
+}
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static ACTIVE: core::cell::RefCell<Option<Table>> = const { core::cell::RefCell::new(None) };
+}
+
+impl<'l, B: Backend> InternerSeed<'l, B> {
+    /// Create an instance deserializing into a storage.
+    #[inline]
+    pub fn new(backend: &'l mut B) -> Self {
+        Self(backend)
+    }
+}
+
+impl<'l, B: Backend> DeserializeSeed<'l> for InternerSeed<'_, B> {
+    type Value = usize;
+
+    #[inline]
+    fn deserialize<T>(self, deserializer: T) -> Result<Self::Value, T::Error>
+    where
+        T: Deserializer<'l>,
+    {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl<'l> Deserialize<'l> for Symbol {
+    #[inline]
+    fn deserialize<T>(deserializer: T) -> Result<Self, T::Error>
+    where
+        T: Deserializer<'l>,
+    {
+        deserializer.deserialize_str(SymbolVisitor)
+    }
+}
+
+impl Serialize for Symbol {
+    #[inline]
+    fn serialize<T>(&self, serializer: T) -> Result<T::Ok, T::Error>
+    where
+        T: Serializer,
+    {
+        serializer.serialize_str(self.as_ref())
+    }
+}
+
+impl<'l> Deserialize<'l> for Entry {
+    #[inline]
+    fn deserialize<T>(deserializer: T) -> Result<Self, T::Error>
+    where
+        T: Deserializer<'l>,
+    {
+        deserializer.deserialize_str(SymbolVisitor).map(Entry)
+    }
+}
+
+impl<'l> Deserialize<'l> for Table {
+    #[inline]
+    fn deserialize<T>(deserializer: T) -> Result<Self, T::Error>
+    where
+        T: Deserializer<'l>,
+    {
+        deserializer.deserialize_seq(TableVisitor)
+    }
+}
+
+impl Serialize for Table {
+    fn serialize<T>(&self, serializer: T) -> Result<T::Ok, T::Error>
+    where
+        T: Serializer,
+    {
+        let mut sequence = serializer.serialize_seq(Some(self.len()))?;
+        for symbol in self.0.iter() {
+            sequence.serialize_element(symbol.as_static_str())?;
+        }
+        sequence.end()
+    }
+}
+
+impl<'l> Visitor<'l> for SymbolVisitor {
+    type Value = Symbol;
+
+    #[inline]
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str("a string")
+    }
+
+    #[inline]
+    fn visit_borrowed_str<T>(self, value: &'l str) -> Result<Self::Value, T>
+    where
+        T: Error,
+    {
+        self.visit_str(value)
+    }
+
+    #[inline]
+    fn visit_str<T>(self, value: &str) -> Result<Self::Value, T>
+    where
+        T: Error,
+    {
+        Symbol::try_new(value).map_err(T::custom)
+    }
+
+    #[inline]
+    fn visit_string<T>(self, value: String) -> Result<Self::Value, T>
+    where
+        T: Error,
+    {
+        Symbol::try_new(value).map_err(T::custom)
+    }
+}
+
+impl<'l, B: Backend> Visitor<'l> for InternerSeed<'_, B> {
+    type Value = usize;
+
+    #[inline]
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str("a string")
+    }
+
+    #[inline]
+    fn visit_str<T>(self, value: &str) -> Result<Self::Value, T>
+    where
+        T: Error,
+    {
+        Ok(self.0.intern(value))
+    }
+}
+
+impl<'l> Visitor<'l> for TableVisitor {
+    type Value = Table;
+
+    #[inline]
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str("a sequence of strings")
+    }
+
+    fn visit_seq<T>(self, mut sequence: T) -> Result<Self::Value, T::Error>
+    where
+        T: SeqAccess<'l>,
+    {
+        let mut symbols = Vec::with_capacity(sequence.size_hint().unwrap_or(0));
+        while let Some(Entry(symbol)) = sequence.next_element()? {
+            symbols.push(symbol);
+        }
+        Ok(Table(symbols.into()))
+    }
+}
+
+/// Serialization of maps with symbols as keys, which are written as strings
+/// regardless of the format.
+///
+/// The module is meant for `#[serde(with = "r#box::serde::intern_keys")]` on
+/// fields such as `HashMap<Symbol, V>` and `BTreeMap<Symbol, V>`. Keys borrowed
+/// from the input are interned without allocation if already stored.
+pub mod intern_keys {
+    use core::fmt::Formatter;
+    use core::marker::PhantomData;
+
+    use ::serde::de::{Deserialize, Deserializer, MapAccess, Visitor};
+    use ::serde::ser::{Serialize, SerializeMap, Serializer};
+
+    use super::Entry;
+    use crate::Symbol;
+
+    struct MapVisitor<M, V>(PhantomData<fn() -> (M, V)>);
+
+    /// Serialize a map.
+    pub fn serialize<'l, M, V, S>(value: &'l M, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        &'l M: IntoIterator<Item = (&'l Symbol, &'l V)>,
+        V: Serialize + 'l,
+        S: Serializer,
+    {
+        let entries = value.into_iter();
+        let mut map = serializer.serialize_map(entries.size_hint().1)?;
+        for (key, value) in entries {
+            map.serialize_entry(key.as_static_str(), value)?;
+        }
+        map.end()
+    }
+
+    /// Deserialize a map.
+    pub fn deserialize<'l, M, V, D>(deserializer: D) -> Result<M, D::Error>
+    where
+        M: Default + Extend<(Symbol, V)>,
+        V: Deserialize<'l>,
+        D: Deserializer<'l>,
+    {
+        deserializer.deserialize_map(MapVisitor(PhantomData))
+    }
+
+    impl<'l, M, V> Visitor<'l> for MapVisitor<M, V>
+    where
+        M: Default + Extend<(Symbol, V)>,
+        V: Deserialize<'l>,
+    {
+        type Value = M;
+
+        #[inline]
+        fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+            formatter.write_str("a map")
+        }
+
+        fn visit_map<T>(self, mut access: T) -> Result<Self::Value, T::Error>
+        where
+            T: MapAccess<'l>,
+        {
+            let mut map = M::default();
+            while let Some((Entry(key), value)) = access.next_entry()? {
+                map.extend([(key, value)]);
+            }
+            Ok(map)
+        }
+    }
+}
+
+/// Serialization of symbols as identifiers.
+///
+/// The module is meant for `#[serde(with = "r#box::serde::with_id")]` on
+/// fields of type `Symbol`. Identifiers are only meaningful within the process
+/// that has produced them, and they are resolved via a `Table` passed along
+/// and activated via `Table::with` when deserializing, without which
+/// deserialization fails.
+///
+/// ## Example
+///
+/// ```
+/// use r#box::serde::{with_id, Table};
+/// use r#box::Symbol;
+///
+/// let one = Symbol::new("foo");
+/// let mut document = Vec::new();
+/// with_id::serialize(&one, &mut serde_json::Serializer::new(&mut document)).unwrap();
+/// let table = serde_json::to_string(&Table::new()).unwrap();
+///
+/// let table: Table = serde_json::from_str(&table).unwrap();
+/// let mut deserializer = serde_json::Deserializer::from_slice(&document);
+/// let other = table.with(|| with_id::deserialize(&mut deserializer)).unwrap();
+/// assert_eq!(other, one);
+/// ```
+#[cfg(feature = "std")]
+pub mod with_id {
+    use core::fmt::Formatter;
+
+    use ::serde::de::{Deserializer, Error, Visitor};
+    use ::serde::ser::Serializer;
+
+    use super::ACTIVE;
+    use crate::{InternError, Symbol};
+
+    struct IdVisitor;
+
+    /// Serialize a symbol.
+    #[inline]
+    pub fn serialize<S>(value: &Symbol, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u64(value.id() as u64)
+    }
+
+    /// Deserialize a symbol.
+    #[inline]
+    pub fn deserialize<'l, D>(deserializer: D) -> Result<Symbol, D::Error>
+    where
+        D: Deserializer<'l>,
+    {
+        deserializer.deserialize_u64(IdVisitor)
+    }
+
+    impl Visitor<'_> for IdVisitor {
+        type Value = Symbol;
+
+        #[inline]
+        fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+            formatter.write_str("an identifier")
+        }
+
+        fn visit_u64<T>(self, value: u64) -> Result<Self::Value, T>
+        where
+            T: Error,
+        {
+            let id = usize::try_from(value).map_err(|_| T::custom(InternError::InvalidId))?;
+            ACTIVE
+                .with(|active| active.borrow().as_ref().and_then(|table| table.get(id)))
+                .ok_or_else(|| T::custom(InternError::InvalidId))
+        }
+    }
+}
+
+/// Serialization of containers of symbols as a table of strings followed by
+/// references into it.
+///
+/// The module is meant for `#[serde(with = "r#box::serde::with_table")]`,
+/// which stores each string once regardless of how many times it occurs and
+/// interns it once when deserializing.
+pub mod with_table {
+    use alloc::vec::Vec;
+
+    use super::Table;
+    use crate::{InternError, Symbol};
+    use ::serde::de::{Deserialize, Deserializer, Error};
+    use ::serde::ser::{Serialize, Serializer};
+
+    /// Serialize a container of symbols.
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        for<'l> &'l T: IntoIterator<Item = &'l Symbol>,
+        S: Serializer,
+    {
+        crate::tabulate(value).serialize(serializer)
+    }
+
+    /// Deserialize a container of symbols.
+    pub fn deserialize<'l, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: FromIterator<Symbol>,
+        D: Deserializer<'l>,
+    {
+        let (table, references) = <(Table, Vec<usize>)>::deserialize(deserializer)?;
+        references
+            .into_iter()
+            .map(|id| {
+                table
+                    .get(id)
+                    .ok_or(D::Error::custom(InternError::InvalidId))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Table;
+    use crate::Symbol;
+
+    #[test]
+    fn borrowed() {
+        let one = Symbol::new("serde-foo");
+        let mut deserializer = serde_json::Deserializer::from_str(r#""serde-foo""#);
+        let other: Symbol = serde::de::Deserialize::deserialize(&mut deserializer).unwrap();
+        assert_eq!(other, one);
+        let other: Symbol = serde_json::from_str(r#""serde-bar""#).unwrap();
+        assert_eq!(other, "serde-bar");
+        assert_eq!(serde_json::to_string(&one).unwrap(), r#""serde-foo""#);
+    }
+
+    #[test]
+    fn compact() {
+        let one = Symbol::new("serde-compact");
+        let mut buffer = Vec::new();
+        ciborium::into_writer(&one, &mut buffer).unwrap();
+        let other: String = ciborium::from_reader(&buffer[..]).unwrap();
+        assert_eq!(other, "serde-compact");
+        let other: Symbol = ciborium::from_reader(&buffer[..]).unwrap();
+        assert_eq!(other, one);
+        let mut buffer = Vec::new();
+        ciborium::into_writer(&(one.id() as u64), &mut buffer).unwrap();
+        assert!(ciborium::from_reader::<Symbol, _>(&buffer[..]).is_err());
+    }
+
+    #[test]
+    fn intern_keys() {
+        use std::collections::{BTreeMap, HashMap};
+
+        use super::intern_keys;
+
+        let value = r#"{"serde-key-foo":1,"serde-key-bar":2}"#;
+        let mut deserializer = serde_json::Deserializer::from_str(value);
+        let map: HashMap<Symbol, u8> = intern_keys::deserialize(&mut deserializer).unwrap();
+        assert_eq!(map[&Symbol::new("serde-key-foo")], 1);
+        let mut deserializer = serde_json::Deserializer::from_str(value);
+        let map: BTreeMap<Symbol, u8> = intern_keys::deserialize(&mut deserializer).unwrap();
+        assert_eq!(map.len(), 2);
+
+        struct Wrapper(BTreeMap<Symbol, u8>);
+
+        impl serde::ser::Serialize for Wrapper {
+            fn serialize<S: serde::ser::Serializer>(
+                &self,
+                serializer: S,
+            ) -> Result<S::Ok, S::Error> {
+                intern_keys::serialize(&self.0, serializer)
+            }
+        }
+
+        let mut buffer = Vec::new();
+        ciborium::into_writer(&Wrapper(map), &mut buffer).unwrap();
+        let other: BTreeMap<String, u8> = ciborium::from_reader(&buffer[..]).unwrap();
+        assert_eq!(other["serde-key-bar"], 2);
+    }
+
+    #[test]
+    fn seed() {
+        use serde::de::DeserializeSeed;
+
+        use super::InternerSeed;
+        use crate::Interner;
+
+        let mut interner = Interner::new();
+        let mut deserializer = serde_json::Deserializer::from_str(r#""serde-seed-foo""#);
+        let id = InternerSeed::new(&mut interner)
+            .deserialize(&mut deserializer)
+            .unwrap();
+        assert_eq!(interner.resolve(id), Some("serde-seed-foo"));
+        assert_eq!(Symbol::get("serde-seed-foo"), None);
+        let mut deserializer = serde_json::Deserializer::from_str("1");
+        assert!(InternerSeed::new(&mut interner)
+            .deserialize(&mut deserializer)
+            .is_err());
+    }
+
+    #[test]
+    fn table() {
+        let one = Symbol::new("serde-table");
+        let table = Table::new();
+        assert_eq!(table.get(one.id()), Some(one));
+        let mut buffer = Vec::new();
+        ciborium::into_writer(&table, &mut buffer).unwrap();
+        let other: Table = ciborium::from_reader(&buffer[..]).unwrap();
+        assert_eq!(other.get(one.id()), Some(one));
+    }
+
+    #[test]
+    fn with_id() {
+        use super::with_id;
+
+        let table = r#"["", "serde-id-foo", "serde-id-bar"]"#;
+        let table: Table = serde_json::from_str(table).unwrap();
+        let mut buffer = Vec::new();
+        let one = Symbol::new("serde-id-baz");
+        with_id::serialize(&one, &mut serde_json::Serializer::new(&mut buffer)).unwrap();
+        assert_eq!(buffer, one.id().to_string().as_bytes());
+        let deserialize =
+            |value| with_id::deserialize(&mut serde_json::Deserializer::from_str(value));
+        assert_eq!(table.with(|| deserialize("1")).unwrap(), "serde-id-foo");
+        assert!(table.with(|| deserialize("3")).is_err());
+        assert!(deserialize("1").is_err());
+    }
+
+    #[test]
+    fn with_table() {
+        use super::with_table;
+
+        let symbols = [
+            Symbol::new("serde-with-foo"),
+            Symbol::new("serde-with-bar"),
+            Symbol::new("serde-with-foo"),
+        ];
+        let mut buffer = Vec::new();
+        with_table::serialize(&symbols, &mut serde_json::Serializer::new(&mut buffer)).unwrap();
+        let value = String::from_utf8(buffer).unwrap();
+        assert_eq!(value, r#"[["serde-with-foo","serde-with-bar"],[0,1,0]]"#);
+        let mut deserializer = serde_json::Deserializer::from_str(&value);
+        let other: Vec<Symbol> = with_table::deserialize(&mut deserializer).unwrap();
+        assert_eq!(other, symbols);
+        let mut deserializer = serde_json::Deserializer::from_str(r#"[["foo"],[1]]"#);
+        assert!(with_table::deserialize::<Vec<Symbol>, _>(&mut deserializer).is_err());
+    }
+}
@@ -0,0 +1,365 @@
+#[cfg(all(
+    feature = "parking_lot",
+    not(all(target_family = "wasm", target_feature = "atomics"))
+))]
+pub use parking_lot::{Mutex, RwLock};
+
+#[cfg(all(
+    feature = "std",
+    not(feature = "parking_lot"),
+    not(all(target_family = "wasm", target_feature = "atomics"))
+))]
+pub use self::standard::{Mutex, RwLock};
+
+#[cfg(all(
+    feature = "std",
+    not(all(target_family = "wasm", target_feature = "atomics"))
+))]
+pub use std::sync::OnceLock;
+
+// Threads on the web cannot block the main thread, so locks spin there too.
+#[cfg(any(
+    not(feature = "std"),
+    all(target_family = "wasm", target_feature = "atomics")
+))]
+pub use self::spin::{Mutex, OnceLock, RwLock};
+
+#[cfg(all(
+    feature = "std",
+    not(feature = "parking_lot"),
+    not(all(target_family = "wasm", target_feature = "atomics"))
+))]
+mod standard {
+    use std::sync::{MutexGuard, PoisonError, RwLockReadGuard, RwLockWriteGuard};
+
+    // A lock that recovers from poisoning, since the protected state is never
+    // left inconsistent by a panic.
+    #[derive(Default)]
+    pub struct Mutex<T>(std::sync::Mutex<T>);
+
+    // A lock that recovers from poisoning, since the protected state is never
+    // left inconsistent by a panic.
+    #[derive(Default)]
+    pub struct RwLock<T>(std::sync::RwLock<T>);
+
+    impl<T> Mutex<T> {
+        #[inline]
+        pub const fn new(value: T) -> Self {
+            Self(std::sync::Mutex::new(value))
+        }
+
+        #[inline]
+        pub fn lock(&self) -> MutexGuard<'_, T> {
+            self.0.lock().unwrap_or_else(PoisonError::into_inner)
+        }
+
+        #[cfg(feature = "async")]
+        #[inline]
+        pub fn try_lock(&self) -> Option<MutexGuard<'_, T>> {
+            recover(self.0.try_lock())
+        }
+    }
+
+    impl<T> RwLock<T> {
+        #[inline]
+        pub fn read(&self) -> RwLockReadGuard<'_, T> {
+            self.0.read().unwrap_or_else(PoisonError::into_inner)
+        }
+
+        #[inline]
+        pub fn write(&self) -> RwLockWriteGuard<'_, T> {
+            self.0.write().unwrap_or_else(PoisonError::into_inner)
+        }
+
+        #[cfg(feature = "async")]
+        #[inline]
+        pub fn try_read(&self) -> Option<RwLockReadGuard<'_, T>> {
+            recover(self.0.try_read())
+        }
+
+        #[cfg(feature = "async")]
+        #[inline]
+        pub fn try_write(&self) -> Option<RwLockWriteGuard<'_, T>> {
+            recover(self.0.try_write())
+        }
+    }
+
+    #[cfg(feature = "async")]
+    #[inline]
+    fn recover<T>(result: Result<T, std::sync::TryLockError<T>>) -> Option<T> {
+        use std::sync::TryLockError;
+
+        match result {
+            Ok(guard) => Some(guard),
+            Err(TryLockError::Poisoned(error)) => Some(error.into_inner()),
+            Err(TryLockError::WouldBlock) => None,
+        }
+    }
+}
+
+// Locks that spin instead of parking threads, for targets without an operating
+// system or where blocking is not allowed.
+#[cfg(any(
+    not(feature = "std"),
+    all(target_family = "wasm", target_feature = "atomics")
+))]
+mod spin {
+    use core::cell::UnsafeCell;
+    use core::mem::MaybeUninit;
+    use core::ops::{Deref, DerefMut};
+    use core::sync::atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering};
+
+    const WRITER: usize = usize::MAX;
+
+    const EMPTY: u8 = 0;
+    const RUNNING: u8 = 1;
+    const READY: u8 = 2;
+
+    #[derive(Default)]
+    pub struct Mutex<T> {
+        locked: AtomicBool,
+        value: UnsafeCell<T>,
+    }
+
+    pub struct MutexGuard<'l, T>(&'l Mutex<T>);
+
+    #[derive(Default)]
+    pub struct RwLock<T> {
+        state: AtomicUsize,
+        value: UnsafeCell<T>,
+    }
+
+    pub struct RwLockReadGuard<'l, T>(&'l RwLock<T>);
+
+    pub struct RwLockWriteGuard<'l, T>(&'l RwLock<T>);
+
+    pub struct OnceLock<T> {
+        state: AtomicU8,
+        value: UnsafeCell<MaybeUninit<T>>,
+    }
+
+    // SAFETY: The value is owned.
+    unsafe impl<T: Send> Send for Mutex<T> {}
+
+    // SAFETY: Access to the value is synchronized by the flag.
+    unsafe impl<T: Send> Sync for Mutex<T> {}
+
+    // SAFETY: The value is owned.
+    unsafe impl<T: Send> Send for RwLock<T> {}
+
+    // SAFETY: Access to the value is synchronized by the state.
+    unsafe impl<T: Send + Sync> Sync for RwLock<T> {}
+
+    // SAFETY: The value is owned.
+    unsafe impl<T: Send> Send for OnceLock<T> {}
+
+    // SAFETY: The value is written once before being published.
+    unsafe impl<T: Send + Sync> Sync for OnceLock<T> {}
+
+    impl<T> Mutex<T> {
+        #[inline]
+        pub const fn new(value: T) -> Self {
+            Self {
+                locked: AtomicBool::new(false),
+                value: UnsafeCell::new(value),
+            }
+        }
+
+        #[inline]
+        pub fn lock(&self) -> MutexGuard<'_, T> {
+            loop {
+                if let Some(guard) = self.try_lock() {
+                    return guard;
+                }
+                core::hint::spin_loop();
+            }
+        }
+
+        #[inline]
+        pub fn try_lock(&self) -> Option<MutexGuard<'_, T>> {
+            self.locked
+                .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+                .ok()
+                .map(|_| MutexGuard(self))
+        }
+    }
+
+    impl<T> RwLock<T> {
+        #[inline]
+        pub fn read(&self) -> RwLockReadGuard<'_, T> {
+            loop {
+                if let Some(guard) = self.try_read() {
+                    return guard;
+                }
+                core::hint::spin_loop();
+            }
+        }
+
+        #[inline]
+        pub fn write(&self) -> RwLockWriteGuard<'_, T> {
+            loop {
+                if let Some(guard) = self.try_write() {
+                    return guard;
+                }
+                core::hint::spin_loop();
+            }
+        }
+
+        #[inline]
+        pub fn try_read(&self) -> Option<RwLockReadGuard<'_, T>> {
+            let state = self.state.load(Ordering::Relaxed);
+            if state >= WRITER - 1 {
+                return None;
+            }
+            self.state
+                .compare_exchange_weak(state, state + 1, Ordering::Acquire, Ordering::Relaxed)
+                .ok()
+                .map(|_| RwLockReadGuard(self))
+        }
+
+        #[inline]
+        pub fn try_write(&self) -> Option<RwLockWriteGuard<'_, T>> {
+            self.state
+                .compare_exchange_weak(0, WRITER, Ordering::Acquire, Ordering::Relaxed)
+                .ok()
+                .map(|_| RwLockWriteGuard(self))
+        }
+    }
+
+    impl<T> OnceLock<T> {
+        #[inline]
+        pub const fn new() -> Self {
+            Self {
+                state: AtomicU8::new(EMPTY),
+                value: UnsafeCell::new(MaybeUninit::uninit()),
+            }
+        }
+
+        #[inline]
+        pub fn get(&self) -> Option<&T> {
+            if self.state.load(Ordering::Acquire) != READY {
+                return None;
+            }
+            // SAFETY: The value has been written before the state was set.
+            Some(unsafe { (*self.value.get()).assume_init_ref() })
+        }
+
+        // A panic during initialization makes other callers spin forever,
+        // which is acceptable, since panics abort on such targets.
+        pub fn get_or_init<F>(&self, initialize: F) -> &T
+        where
+            F: FnOnce() -> T,
+        {
+            let ordering = (Ordering::Acquire, Ordering::Acquire);
+            match self
+                .state
+                .compare_exchange(EMPTY, RUNNING, ordering.0, ordering.1)
+            {
+                Ok(_) => {
+                    // SAFETY: The state grants exclusive access.
+                    unsafe { (*self.value.get()).write(initialize()) };
+                    self.state.store(READY, Ordering::Release);
+                }
+                Err(_) => {
+                    while self.state.load(Ordering::Acquire) != READY {
+                        core::hint::spin_loop();
+                    }
+                }
+            }
+            // SAFETY: The value has been written before the state was set.
+            unsafe { (*self.value.get()).assume_init_ref() }
+        }
+
+        pub fn set(&self, value: T) -> Result<(), T> {
+            let mut value = Some(value);
+            self.get_or_init(|| value.take().unwrap());
+            match value {
+                Some(value) => Err(value),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    impl<T> Default for OnceLock<T> {
+        #[inline]
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl<T> Drop for OnceLock<T> {
+        fn drop(&mut self) {
+            if *self.state.get_mut() == READY {
+                // SAFETY: The value has been written.
+                unsafe { self.value.get_mut().assume_init_drop() };
+            }
+        }
+    }
+
+    impl<T> Deref for MutexGuard<'_, T> {
+        type Target = T;
+
+        #[inline]
+        fn deref(&self) -> &T {
+            // SAFETY: The guard grants exclusive access.
+            unsafe { &*self.0.value.get() }
+        }
+    }
+
+    impl<T> DerefMut for MutexGuard<'_, T> {
+        #[inline]
+        fn deref_mut(&mut self) -> &mut T {
+            // SAFETY: The guard grants exclusive access.
+            unsafe { &mut *self.0.value.get() }
+        }
+    }
+
+    impl<T> Drop for MutexGuard<'_, T> {
+        #[inline]
+        fn drop(&mut self) {
+            self.0.locked.store(false, Ordering::Release);
+        }
+    }
+
+    impl<T> Deref for RwLockReadGuard<'_, T> {
+        type Target = T;
+
+        #[inline]
+        fn deref(&self) -> &T {
+            // SAFETY: The guard grants shared access.
+            unsafe { &*self.0.value.get() }
+        }
+    }
+
+    impl<T> Drop for RwLockReadGuard<'_, T> {
+        #[inline]
+        fn drop(&mut self) {
+            self.0.state.fetch_sub(1, Ordering::Release);
+        }
+    }
+
+    impl<T> Deref for RwLockWriteGuard<'_, T> {
+        type Target = T;
+
+        #[inline]
+        fn deref(&self) -> &T {
+            // SAFETY: The guard grants exclusive access.
+            unsafe { &*self.0.value.get() }
+        }
+    }
+
+    impl<T> DerefMut for RwLockWriteGuard<'_, T> {
+        #[inline]
+        fn deref_mut(&mut self) -> &mut T {
+            // SAFETY: The guard grants exclusive access.
+            unsafe { &mut *self.0.value.get() }
+        }
+    }
+
+    impl<T> Drop for RwLockWriteGuard<'_, T> {
+        #[inline]
+        fn drop(&mut self) {
+            self.0.state.store(0, Ordering::Release);
+        }
+    }
+}
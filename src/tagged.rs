@@ -0,0 +1,236 @@
+//! Symbols separated by domain.
+
+use alloc::string::String;
+use core::hash::BuildHasher;
+use core::marker::PhantomData;
+
+use crate::{Arena, InternError, Pool, Symbol};
+
+/// A domain with its own storage.
+///
+/// ## Example
+///
+/// ```
+/// use r#box::{Pool, Tag, TaggedSymbol};
+///
+/// struct Path;
+///
+/// impl Tag for Path {
+///     fn pool() -> &'static Pool {
+///         static POOL: Pool = Pool::new();
+///         &POOL
+///     }
+/// }
+///
+/// let path = TaggedSymbol::<Path>::new("src/lib.rs");
+/// assert_eq!(path, TaggedSymbol::<Path>::new("src/lib.rs"));
+/// assert_eq!(path.id(), 1);
+/// ```
+pub trait Tag: 'static {
+    /// Return the storage of the domain.
+    fn pool() -> &'static Pool;
+}
+
+/// A unique static string within a domain.
+///
+/// Symbols of different domains are different types and are backed by
+/// different storages, so that they cannot be mixed up and their identifiers
+/// stay dense within each domain.
+pub struct TaggedSymbol<T> {
+    symbol: Symbol,
+    tag: PhantomData<fn() -> T>,
+}
+
+impl<T: Tag> TaggedSymbol<T> {
+    /// The empty string.
+    pub const EMPTY: Self = Self::from_symbol(Symbol::EMPTY);
+
+    /// Create a new instance.
+    ///
+    /// The function panics if the storage cannot accommodate the string.
+    #[inline]
+    #[cfg_attr(feature = "debug-tracking", track_caller)]
+    pub fn new<U>(value: U) -> Self
+    where
+        U: AsRef<str> + Into<String>,
+    {
+        match Self::try_new(value) {
+            Ok(symbol) => symbol,
+            Err(error) => panic!("{error}"),
+        }
+    }
+
+    /// Create a new instance unless the storage cannot accommodate the string.
+    #[inline]
+    #[cfg_attr(feature = "debug-tracking", track_caller)]
+    pub fn try_new<U>(value: U) -> Result<Self, InternError>
+    where
+        U: AsRef<str> + Into<String>,
+    {
+        let state = T::pool().state();
+        let hash = state.hasher.hash_one(value.as_ref());
+        state
+            .intern(hash, value, Arena::store)
+            .map(Self::from_symbol)
+    }
+
+    /// Find an existing instance without creating one.
+    #[inline]
+    pub fn get(value: &str) -> Option<Self> {
+        let state = T::pool().state();
+        let hash = state.hasher.hash_one(value);
+        state.find(hash, value).map(Self::from_symbol)
+    }
+
+    /// Return the number of instances, including the empty one.
+    #[inline]
+    pub fn count() -> usize {
+        T::pool().len()
+    }
+
+    /// Return the identifier within the domain.
+    #[inline]
+    pub fn id(&self) -> usize {
+        self.symbol.id()
+    }
+
+    /// Return the underlying string.
+    #[inline]
+    pub fn as_static_str(&self) -> &'static str {
+        T::pool().state().values.get(self.id()).unwrap()
+    }
+
+    #[inline]
+    const fn from_symbol(symbol: Symbol) -> Self {
+        Self {
+            symbol,
+            tag: PhantomData,
+        }
+    }
+}
+
+impl<T: Tag> AsRef<str> for TaggedSymbol<T> {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_static_str()
+    }
+}
+
+impl<T> Clone for TaggedSymbol<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for TaggedSymbol<T> {}
+
+impl<T: Tag> Default for TaggedSymbol<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::EMPTY
+    }
+}
+
+impl<T: Tag> core::borrow::Borrow<str> for TaggedSymbol<T> {
+    #[inline]
+    fn borrow(&self) -> &str {
+        self.as_static_str()
+    }
+}
+
+impl<T> core::cmp::Eq for TaggedSymbol<T> {}
+
+impl<T: Tag> core::cmp::Ord for TaggedSymbol<T> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        if self.symbol == other.symbol {
+            return core::cmp::Ordering::Equal;
+        }
+        self.as_static_str().cmp(other.as_static_str())
+    }
+}
+
+impl<T> core::cmp::PartialEq for TaggedSymbol<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.symbol == other.symbol
+    }
+}
+
+impl<T: Tag> core::cmp::PartialOrd for TaggedSymbol<T> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Tag> core::fmt::Debug for TaggedSymbol<T> {
+    fn fmt(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self.as_static_str(), formatter)
+    }
+}
+
+impl<T: Tag> core::fmt::Display for TaggedSymbol<T> {
+    #[inline]
+    fn fmt(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(self.as_static_str(), formatter)
+    }
+}
+
+impl<T: Tag> core::hash::Hash for TaggedSymbol<T> {
+    #[inline]
+    fn hash<U: core::hash::Hasher>(&self, state: &mut U) {
+        self.as_static_str().hash(state)
+    }
+}
+
+impl<T: Tag> core::ops::Deref for TaggedSymbol<T> {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_static_str()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Tag, TaggedSymbol};
+    use crate::{Pool, Symbol};
+
+    struct Identifier;
+
+    impl Tag for Identifier {
+        fn pool() -> &'static Pool {
+            static POOL: Pool = Pool::new();
+            &POOL
+        }
+    }
+
+    struct Literal;
+
+    impl Tag for Literal {
+        fn pool() -> &'static Pool {
+            static POOL: Pool = Pool::new();
+            &POOL
+        }
+    }
+
+    #[test]
+    fn separate() {
+        let identifier = TaggedSymbol::<Identifier>::new("tagged-foo");
+        let literal = TaggedSymbol::<Literal>::new("tagged-bar");
+        assert_eq!(identifier.id(), literal.id());
+        assert_eq!(identifier.as_static_str(), "tagged-foo");
+        assert_eq!(literal.as_static_str(), "tagged-bar");
+        assert_eq!(
+            TaggedSymbol::<Identifier>::get("tagged-foo"),
+            Some(identifier)
+        );
+        assert_eq!(TaggedSymbol::<Identifier>::get("tagged-bar"), None);
+        assert_eq!(TaggedSymbol::<Literal>::new(""), TaggedSymbol::EMPTY);
+        assert_eq!(TaggedSymbol::<Literal>::count(), 2);
+        assert_eq!(Symbol::get("tagged-foo"), None);
+    }
+}
@@ -0,0 +1,95 @@
+//! Recording via tracing.
+//!
+//! `Value` cannot be implemented outside of tracing, so symbols are recorded
+//! as static strings, either via `as_static_str` in the macros or via the
+//! functions below, none of which allocates.
+
+use ::tracing::field::AsField;
+use ::tracing::Span;
+
+use crate::Symbol;
+
+/// Record the string of a symbol in a field of a span.
+#[inline]
+pub fn record_symbol<'l, Q>(span: &'l Span, field: &Q, symbol: Symbol) -> &'l Span
+where
+    Q: AsField + ?Sized,
+{
+    span.record(field, symbol.as_static_str())
+}
+
+/// Record the identifier of a symbol in a field of a span.
+///
+/// The identifier can be stored in place of the string for deduplication
+/// downstream.
+#[inline]
+pub fn record_symbol_id<'l, Q>(span: &'l Span, field: &Q, symbol: Symbol) -> &'l Span
+where
+    Q: AsField + ?Sized,
+{
+    span.record(field, symbol.id() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    use super::{record_symbol, record_symbol_id};
+    use crate::Symbol;
+
+    #[derive(Clone, Default)]
+    struct Recorder(Arc<Mutex<Vec<String>>>);
+
+    impl Visit for Recorder {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.0.lock().unwrap().push(format!("{field}={value}"));
+        }
+
+        fn record_u64(&mut self, field: &Field, value: u64) {
+            self.0.lock().unwrap().push(format!("{field}={value}"));
+        }
+
+        fn record_debug(&mut self, _: &Field, _: &dyn std::fmt::Debug) {}
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, _: &Attributes<'_>) -> Id {
+            Id::from_u64(1)
+        }
+
+        fn record(&self, _: &Id, values: &Record<'_>) {
+            values.record(&mut self.clone());
+        }
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, _: &Event<'_>) {}
+
+        fn enter(&self, _: &Id) {}
+
+        fn exit(&self, _: &Id) {}
+    }
+
+    #[test]
+    fn record() {
+        let one = Symbol::new("tracing-foo");
+        let recorder = Recorder::default();
+        tracing::subscriber::with_default(recorder.clone(), || {
+            let span = tracing::info_span!("span", name = tracing::field::Empty, id = 0u64);
+            record_symbol(&span, "name", one);
+            record_symbol_id(&span, "id", one);
+        });
+        assert_eq!(
+            *recorder.0.lock().unwrap(),
+            ["name=tracing-foo".to_string(), format!("id={}", one.id()),],
+        );
+    }
+}
@@ -0,0 +1,38 @@
+//! Tracking of the call sites creating symbols.
+
+use alloc::vec::Vec;
+use core::panic::Location;
+use hashbrown::HashMap;
+
+use crate::sync::Mutex;
+use crate::Hasher;
+
+type Site = &'static Location<'static>;
+
+static SITES: Mutex<Option<HashMap<Site, usize, Hasher>>> = Mutex::new(None);
+
+/// Return the call sites that have created the most symbols along with the
+/// numbers of symbols created, in descending order.
+///
+/// Only the symbols created via functions called directly are attributed to
+/// the call sites, and the symbols created via closures or asynchronous
+/// functions are attributed to the call sites within the crate.
+pub fn top_sites(count: usize) -> Vec<(Site, usize)> {
+    let sites = SITES.lock();
+    let mut sites = sites
+        .iter()
+        .flatten()
+        .map(|(&site, &count)| (site, count))
+        .collect::<Vec<_>>();
+    sites.sort_unstable_by_key(|&(_, count)| core::cmp::Reverse(count));
+    sites.truncate(count);
+    sites
+}
+
+pub fn record(site: Site) {
+    let mut sites = SITES.lock();
+    *sites
+        .get_or_insert_with(Default::default)
+        .entry(site)
+        .or_default() += 1;
+}
@@ -0,0 +1,18 @@
+use r#box::{Config, ConfigureError, InternError, Symbol};
+
+#[test]
+fn configure() {
+    let config = Config {
+        max_symbols: 3,
+        case_folding: true,
+        ..Default::default()
+    };
+    assert_eq!(r#box::configure(config), Ok(()));
+    assert_eq!(r#box::configure(config), Err(ConfigureError));
+    let one = Symbol::new("Foo");
+    assert_eq!(one, "foo");
+    assert_eq!(Symbol::new("FOO"), one);
+    assert_eq!(Symbol::get("fOO"), Some(one));
+    assert_eq!(Symbol::new_all(["BAR", "bar"]), [Symbol::new("bar"); 2]);
+    assert_eq!(Symbol::try_new("baz"), Err(InternError::CapacityExceeded));
+}
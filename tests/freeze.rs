@@ -0,0 +1,13 @@
+use r#box::{InternError, Symbol};
+
+#[test]
+fn freeze() {
+    let one = Symbol::new("foo");
+    r#box::freeze();
+    assert_eq!(Symbol::new("foo"), one);
+    assert_eq!(Symbol::get("foo"), Some(one));
+    assert_eq!(Symbol::try_new("bar"), Err(InternError::Frozen));
+    assert_eq!(Symbol::get("bar"), None);
+    assert!(std::panic::catch_unwind(|| Symbol::new("bar")).is_err());
+    assert!(std::panic::catch_unwind(|| Symbol::new_all(["bar"])).is_err());
+}
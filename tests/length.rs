@@ -0,0 +1,22 @@
+use r#box::{Config, InternError, Symbol};
+
+#[test]
+fn length() {
+    let config = Config {
+        max_length: 3,
+        ..Default::default()
+    };
+    assert_eq!(r#box::configure(config), Ok(()));
+    assert_eq!(Symbol::try_new("foo"), Ok(Symbol::new("foo")));
+    assert_eq!(Symbol::try_new("foobar"), Err(InternError::TooLong));
+    assert_eq!(Symbol::try_new_static("bar").unwrap(), "bar");
+    assert_eq!(
+        Symbol::try_new_all(["baz", "bazqux"]),
+        Err(InternError::TooLong),
+    );
+    assert_eq!(Symbol::get("baz"), Some(Symbol::new("baz")));
+    assert_eq!(
+        Symbol::alias("quux", Symbol::new("foo")),
+        Err(InternError::TooLong),
+    );
+}
@@ -0,0 +1,26 @@
+use r#box::{FromUtf16Error, InternError, Symbol};
+
+#[test]
+fn limit() {
+    let count = Symbol::count();
+    r#box::limit(count + 1, usize::MAX);
+    let one = Symbol::new("foo");
+    assert_eq!(Symbol::try_new("foo"), Ok(one));
+    assert_eq!(Symbol::try_new("bar"), Err(InternError::CapacityExceeded));
+    assert_eq!(Symbol::try_new(""), Ok(Symbol::EMPTY));
+    assert_eq!(
+        Symbol::try_new_all(["foo", "bar"]),
+        Err(InternError::CapacityExceeded),
+    );
+    let value = "bar".encode_utf16().collect::<Vec<_>>();
+    assert_eq!(
+        Symbol::try_from_utf16(&value),
+        Err(FromUtf16Error::Intern(InternError::CapacityExceeded)),
+    );
+    assert_eq!(Symbol::alias("baz", one), Ok(()));
+    r#box::limit(usize::MAX, r#box::stats().bytes);
+    assert_eq!(
+        Symbol::alias("qux", one),
+        Err(InternError::CapacityExceeded),
+    );
+}
@@ -0,0 +1,51 @@
+#![cfg(feature = "macros")]
+
+use r#box::{FieldSymbols, Symbol, SymbolEnum};
+
+mod keywords {
+    r#box::include_symbols!("tests/data/keywords.txt");
+}
+
+#[test]
+fn field_symbols() {
+    #[allow(dead_code)]
+    #[derive(FieldSymbols)]
+    struct Request {
+        method: Symbol,
+        #[symbol(rename = "content-type")]
+        content_type: Symbol,
+        r#type: u8,
+    }
+
+    let fields = Request::fields().iter().map(|field| field.get());
+    assert_eq!(
+        fields.collect::<Vec<_>>(),
+        ["method", "content-type", "type"]
+    );
+    assert_eq!(Request::content_type_symbol(), "content-type");
+    assert_eq!(Request::type_symbol(), "type");
+    assert_eq!(Request::field_index(Symbol::new("type")), Some(2));
+    assert_eq!(Request::field_index(Symbol::new("content_type")), None);
+}
+
+#[test]
+fn include_symbols() {
+    assert_eq!(*keywords::GET, Symbol::new("GET"));
+    assert_eq!(keywords::POST.get(), "POST");
+    assert_eq!(*keywords::CONTENT_TYPE, "content-type");
+}
+
+#[test]
+fn symbol_enum() {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, SymbolEnum)]
+    enum Method {
+        Get,
+        #[symbol(rename = "POST")]
+        Post,
+    }
+
+    assert_eq!(Method::Get.symbol(), "Get");
+    assert_eq!(Method::Post.symbol(), Symbol::new("POST"));
+    assert_eq!(Method::from_symbol(Symbol::new("POST")), Some(Method::Post));
+    assert_eq!(Method::from_symbol(Symbol::new("Post")), None);
+}
@@ -0,0 +1,20 @@
+#![cfg(feature = "normalization")]
+
+use r#box::{Config, Symbol};
+
+#[test]
+fn normalization() {
+    let config = Config {
+        normalization: true,
+        ..Default::default()
+    };
+    assert_eq!(r#box::configure(config), Ok(()));
+    let one = Symbol::new("caf\u{65}\u{301}");
+    assert_eq!(one, "caf\u{e9}");
+    assert_eq!(Symbol::new("caf\u{e9}"), one);
+    assert_eq!(Symbol::get("caf\u{65}\u{301}"), Some(one));
+    assert_eq!(
+        Symbol::new_all(["\u{212b}", "\u{41}\u{30a}"]),
+        [Symbol::new("\u{c5}"); 2],
+    );
+}
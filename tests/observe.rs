@@ -0,0 +1,22 @@
+use std::sync::{Arc, Mutex};
+
+use r#box::Symbol;
+
+#[test]
+fn observe() {
+    let created = Arc::new(Mutex::new(Vec::new()));
+    let other = created.clone();
+    r#box::observe(move |value, id| other.lock().unwrap().push((value, id)));
+    let foo = Symbol::new("foo");
+    Symbol::new("foo");
+    let symbols = Symbol::new_all(["bar", "foo", "baz"]);
+    let created = created.lock().unwrap();
+    assert_eq!(
+        *created,
+        [
+            ("foo", foo.id()),
+            ("bar", symbols[0].id()),
+            ("baz", symbols[2].id()),
+        ],
+    );
+}
@@ -0,0 +1,16 @@
+#![cfg(feature = "quickcheck")]
+
+use quickcheck::{Arbitrary, Gen};
+use r#box::Symbol;
+
+#[test]
+fn quickcheck() {
+    let mut generator = Gen::new(10);
+    for _ in 0..100 {
+        assert!(Symbol::arbitrary(&mut generator).chars().count() < 10);
+    }
+    let symbol = Symbol::new("foo");
+    assert!(symbol.shrink().any(|other| other == Symbol::EMPTY));
+    assert!(symbol.shrink().all(|other| other != symbol));
+    assert!(Symbol::EMPTY.shrink().next().is_none());
+}
@@ -0,0 +1,26 @@
+use std::sync::{Arc, Barrier};
+
+use r#box::RcSymbol;
+
+#[test]
+fn release() {
+    let threads = 2;
+    for _ in 0..20000 {
+        let barrier = Arc::new(Barrier::new(threads));
+        let one = RcSymbol::new("foo");
+        let handles = (0..threads)
+            .map(|_| {
+                let (barrier, one) = (barrier.clone(), one.clone());
+                std::thread::spawn(move || {
+                    barrier.wait();
+                    drop(one);
+                })
+            })
+            .collect::<Vec<_>>();
+        drop(one);
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(RcSymbol::count(), 0);
+    }
+}
@@ -0,0 +1,9 @@
+use r#box::Symbol;
+
+#[test]
+fn reserve() {
+    r#box::reserve(1000, 10 * 1000);
+    let symbols = (0..1000).map(|index| Symbol::new(format!("reserve-{index}")));
+    let symbols = symbols.collect::<Vec<_>>();
+    assert_eq!(symbols[42], "reserve-42");
+}
@@ -0,0 +1,16 @@
+use r#box::{Config, Symbol};
+
+#[test]
+fn reserved() {
+    let config = Config {
+        reserved: &["GET", "POST", "PUT"],
+        ..Default::default()
+    };
+    r#box::configure(config).unwrap();
+    assert_eq!(Symbol::new("PUT").id(), 3);
+    assert_eq!(Symbol::new("DELETE").id(), 4);
+    assert_eq!(Symbol::new("GET").id(), 1);
+    assert_eq!(Symbol::from_id(2).unwrap(), "POST");
+    const POST: Symbol = Symbol::reserved(2);
+    assert_eq!(POST, "POST");
+}
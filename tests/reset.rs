@@ -0,0 +1,30 @@
+#![cfg(feature = "testing")]
+
+use r#box::Symbol;
+
+#[test]
+fn reset() {
+    Symbol::new("foo");
+    Symbol::new("bar");
+    r#box::limit(3, 6);
+    r#box::reset();
+    assert_eq!(Symbol::count(), 1);
+    assert_eq!(Symbol::get("foo"), None);
+    assert_eq!(Symbol::new("").id(), 0);
+    assert_eq!(Symbol::new("baz").id(), 1);
+    assert_eq!(Symbol::new("qux").id(), 2);
+    assert_eq!(Symbol::new("quux").id(), 3);
+    assert_eq!(Symbol::new("baz").as_static_str(), "baz");
+    assert_eq!(r#box::stats().bytes, 10);
+
+    #[cfg(feature = "unicode-folding")]
+    {
+        let one = Symbol::new("Corge");
+        assert_eq!(one.folded(), "corge");
+        r#box::reset();
+        Symbol::new_all(["garply", "waldo", "fred"]);
+        let other = Symbol::new("Grault");
+        assert_eq!(other.id(), one.id());
+        assert_eq!(other.folded(), "grault");
+    }
+}
@@ -0,0 +1,24 @@
+#![cfg(feature = "testing")]
+
+use r#box::{Config, InternError, Symbol};
+
+#[test]
+fn reset_config() {
+    let config = Config {
+        max_length: 4,
+        reserved: &["GET", "POST"],
+        ..Default::default()
+    };
+    r#box::configure(config).unwrap();
+    const POST: Symbol = Symbol::reserved(2);
+    Symbol::new("foo");
+    r#box::limit(4, usize::MAX);
+    r#box::reset();
+    assert_eq!(Symbol::count(), 3);
+    assert_eq!(POST, "POST");
+    assert_eq!(Symbol::get("POST"), Some(POST));
+    assert_eq!(Symbol::get("foo"), None);
+    assert_eq!(Symbol::new("PUT").id(), 3);
+    assert_eq!(Symbol::new("HEAD").id(), 4);
+    assert_eq!(Symbol::try_new("DELETE"), Err(InternError::TooLong));
+}
@@ -0,0 +1,42 @@
+use r#box::Symbol;
+
+#[test]
+fn rollback() {
+    let one = Symbol::new("foo");
+    let checkpoint = r#box::checkpoint();
+    let other = Symbol::new("bar");
+    assert_eq!(Symbol::new("bar"), other);
+    r#box::rollback(checkpoint);
+    assert!(one.is_valid());
+    assert!(!other.is_valid());
+    assert_eq!(other.as_static_str(), "bar");
+    assert_eq!(Symbol::get("foo"), Some(one));
+    assert_eq!(Symbol::get("bar"), None);
+    let another = Symbol::new("bar");
+    assert_ne!(another, other);
+    assert_ne!(another.cmp(&other), std::cmp::Ordering::Equal);
+    assert!(another.is_valid());
+    assert_eq!(r#box::stats().bytes, 6);
+
+    let checkpoint = r#box::checkpoint();
+    let third = Symbol::new("baz");
+    r#box::rollback(r#box::checkpoint());
+    assert!(third.is_valid());
+    r#box::rollback(checkpoint);
+    r#box::rollback(checkpoint);
+    assert!(!third.is_valid());
+    assert!(another.is_valid());
+    assert!(!other.is_valid());
+
+    #[cfg(feature = "unicode-folding")]
+    {
+        let one = Symbol::new("Qux");
+        let checkpoint = r#box::checkpoint();
+        let other = one.folded();
+        assert_eq!(other, "qux");
+        r#box::rollback(checkpoint);
+        assert!(!other.is_valid());
+        assert!(one.folded().is_valid());
+        assert_eq!(one.folded(), "qux");
+    }
+}
@@ -0,0 +1,12 @@
+use r#box::Symbol;
+
+#[test]
+fn shrink_to_fit() {
+    r#box::reserve(10000, 0);
+    let symbol = Symbol::new("foo");
+    let one = r#box::stats();
+    r#box::shrink_to_fit();
+    let other = r#box::stats();
+    assert!(other.overhead < one.overhead);
+    assert_eq!(Symbol::new("foo"), symbol);
+}
@@ -0,0 +1,19 @@
+#![cfg(feature = "debug-tracking")]
+
+use r#box::Symbol;
+
+#[test]
+fn top_sites() {
+    let line = line!() + 2;
+    for index in 0..3 {
+        Symbol::new(format!("foo-{index}"));
+    }
+    Symbol::new("bar");
+    Symbol::new_all(["baz", "qux"]);
+    let sites = r#box::top_sites(2);
+    assert_eq!(sites.len(), 2);
+    assert_eq!(sites[0].0.file(), file!());
+    assert_eq!(sites[0].0.line(), line);
+    assert_eq!(sites[0].1, 3);
+    assert_eq!(sites[1].1, 2);
+}